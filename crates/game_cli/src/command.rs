@@ -0,0 +1,113 @@
+use std::{path::PathBuf, time::Duration};
+
+use game_core::solver::{Solver, SolverMode};
+
+use crate::{commands, error::CliError, format::Format};
+
+pub const USAGE: &str = "Usage: pushin_boxes-cli <command> [options] <paths>...
+
+Paths can be level files (.lvl, .xsb, .sok) or directories with level files.
+
+Commands:
+  validate                   Check that the levels can be played
+  solve                      Print a solution and the difficulty of every level
+      --pushes               Find the solution with the fewest pushes instead of moves
+      --max-nodes <nodes>    Give up after exploring this many positions
+      --max-time <seconds>   Give up after this many seconds per level
+  render                     Print the levels as XSB text
+  convert --to <lvl|xsb>     Write every level next to its file in the given format
+  stats                      Print the size and contents of every level
+  help                       Print this message";
+
+pub enum Command {
+    Validate(Vec<PathBuf>),
+    Solve(Solver, Vec<PathBuf>),
+    Render(Vec<PathBuf>),
+    Convert(Format, Vec<PathBuf>),
+    Stats(Vec<PathBuf>),
+    Help,
+}
+
+impl Command {
+    pub fn parse(args: &[String]) -> Result<Command, CliError> {
+        let Some((name, args)) = args.split_first() else {
+            return Ok(Command::Help);
+        };
+
+        let mut paths = vec![];
+        let mut mode = SolverMode::Moves;
+        let mut max_nodes = None;
+        let mut max_time = None;
+        let mut format = None;
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--pushes" => mode = SolverMode::Pushes,
+                "--max-nodes" => max_nodes = Some(parse_value::<usize>(arg, args.next())?),
+                "--max-time" => max_time = Some(parse_value::<f64>(arg, args.next())?),
+                "--to" => {
+                    let value = args.next().ok_or_else(|| missing_value(arg))?;
+                    format = Some(Format::parse(value)?);
+                }
+                _ if arg.starts_with("--") => {
+                    return Err(CliError::Usage(format!("Unknown option '{arg}'")));
+                }
+                _ => paths.push(PathBuf::from(arg)),
+            }
+        }
+
+        let help = matches!(name.as_str(), "help" | "--help" | "-h");
+        if paths.is_empty() && !help {
+            return Err(CliError::Usage(format!("No paths given to '{name}'")));
+        }
+
+        match name.as_str() {
+            "validate" => Ok(Command::Validate(paths)),
+            "solve" => {
+                let mut solver = Solver::new(mode);
+                if let Some(max_nodes) = max_nodes {
+                    solver = solver.max_nodes(max_nodes);
+                }
+                if let Some(max_time) = max_time {
+                    solver = solver.max_time(Duration::from_secs_f64(max_time));
+                }
+                Ok(Command::Solve(solver, paths))
+            }
+            "render" => Ok(Command::Render(paths)),
+            "convert" => {
+                let format = format
+                    .ok_or_else(|| CliError::Usage("'convert' needs a --to format".to_string()))?;
+                Ok(Command::Convert(format, paths))
+            }
+            "stats" => Ok(Command::Stats(paths)),
+            _ if help => Ok(Command::Help),
+            _ => Err(CliError::Usage(format!("Unknown command '{name}'"))),
+        }
+    }
+
+    pub fn run(&self) -> Result<bool, CliError> {
+        match self {
+            Command::Validate(paths) => commands::validate(paths),
+            Command::Solve(solver, paths) => commands::solve(solver, paths),
+            Command::Render(paths) => commands::render(paths),
+            Command::Convert(format, paths) => commands::convert(*format, paths),
+            Command::Stats(paths) => commands::stats(paths),
+            Command::Help => {
+                println!("{USAGE}");
+                Ok(true)
+            }
+        }
+    }
+}
+
+fn missing_value(option: &str) -> CliError {
+    CliError::Usage(format!("The option '{option}' needs a value"))
+}
+
+fn parse_value<T: std::str::FromStr>(option: &str, value: Option<&String>) -> Result<T, CliError> {
+    let value = value.ok_or_else(|| missing_value(option))?;
+    value
+        .parse()
+        .map_err(|_| CliError::Usage(format!("Invalid value '{value}' for '{option}'")))
+}
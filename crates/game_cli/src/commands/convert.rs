@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use crate::{error::CliError, files, format::Format};
+
+/// Every level is written next to its file, with the extension of the format
+pub fn convert(format: Format, paths: &[PathBuf]) -> Result<bool, CliError> {
+    let mut converted = true;
+
+    for path in files::expand(paths)? {
+        if Format::from_path(&path) == Some(format) {
+            continue;
+        }
+
+        let levels = files::load(&path);
+        let single = levels.len() == 1;
+
+        for (index, level) in levels.into_iter().enumerate() {
+            let output = if single {
+                level.path.with_extension(format.extension())
+            } else {
+                let stem = level
+                    .path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                level
+                    .path
+                    .with_file_name(format!("{stem}-{}.{}", index + 1, format.extension()))
+            };
+
+            match level.state.and_then(|state| files::save(&output, &state)) {
+                Ok(()) => println!("{} -> {}", level.name, output.display()),
+                Err(error) => {
+                    converted = false;
+                    println!("{}: {error}", level.name);
+                }
+            }
+        }
+    }
+
+    Ok(converted)
+}
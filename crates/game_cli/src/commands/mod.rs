@@ -0,0 +1,11 @@
+mod convert;
+mod render;
+mod solve;
+mod stats;
+mod validate;
+
+pub use convert::convert;
+pub use render::render;
+pub use solve::solve;
+pub use stats::stats;
+pub use validate::validate;
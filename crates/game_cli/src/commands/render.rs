@@ -0,0 +1,21 @@
+use std::path::PathBuf;
+
+use crate::{error::CliError, files};
+
+pub fn render(paths: &[PathBuf]) -> Result<bool, CliError> {
+    let mut rendered = true;
+
+    for path in files::expand(paths)? {
+        for level in files::load(&path) {
+            match level.state {
+                Ok(state) => println!("; {}\n{}", level.name, state.to_xsb()),
+                Err(error) => {
+                    rendered = false;
+                    eprintln!("{}: {error}", level.name);
+                }
+            }
+        }
+    }
+
+    Ok(rendered)
+}
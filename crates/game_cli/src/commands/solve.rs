@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use game_core::{
+    level::{Difficulty, Level, LevelKind, UndoPolicy},
+    solver::Solver,
+};
+
+use crate::{error::CliError, files};
+
+pub fn solve(solver: &Solver, paths: &[PathBuf]) -> Result<bool, CliError> {
+    let mut solved = true;
+
+    for path in files::expand(paths)? {
+        for level in files::load(&path) {
+            let solution = level
+                .state
+                .and_then(|state| Ok((solver.solve(&state).map_err(CliError::Solver)?, state)));
+
+            match solution {
+                Ok((solution, state)) => {
+                    let difficulty = Difficulty::new(&state, &solution);
+
+                    // replaying the solution tells apart the moves that push a box
+                    let mut replay = Level::new(LevelKind::default(), state)
+                        .with_undo_policy(UndoPolicy::Unlimited);
+                    for input in solution.inputs() {
+                        replay.update(&input);
+                    }
+
+                    println!(
+                        "{}: {} moves, {} pushes, {} ({})\n{}",
+                        level.name,
+                        solution.moves(),
+                        solution.pushes(),
+                        difficulty.label(),
+                        difficulty.score(),
+                        replay.lurd()
+                    );
+                }
+                Err(error) => {
+                    solved = false;
+                    println!("{}: {error}", level.name);
+                }
+            }
+        }
+    }
+
+    Ok(solved)
+}
@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+
+use game_core::map::MapEntity;
+
+use crate::{error::CliError, files};
+
+pub fn stats(paths: &[PathBuf]) -> Result<bool, CliError> {
+    let mut read = true;
+
+    for path in files::expand(paths)? {
+        for level in files::load(&path) {
+            let state = match level.state {
+                Ok(state) => state,
+                Err(error) => {
+                    read = false;
+                    eprintln!("{}: {error}", level.name);
+                    continue;
+                }
+            };
+
+            let map = state.map();
+            let count = |f: fn(&MapEntity) -> bool| {
+                map.positions().filter(|position| f(&map[position])).count()
+            };
+            let floor = count(|entity| !matches!(entity, MapEntity::V));
+            let boxes = count(|entity| entity.has_box());
+            let zones = count(|entity| matches!(entity, MapEntity::Z | MapEntity::P));
+            let placed = count(|entity| matches!(entity, MapEntity::P));
+            let ice = count(|entity| entity.is_ice());
+            let teleporters = count(|entity| entity.is_teleporter());
+            let plates = count(|entity| entity.is_plate());
+            let doors = count(|entity| matches!(entity, MapEntity::D));
+            let holes = count(|entity| matches!(entity, MapEntity::H));
+
+            println!(
+                "{}: {}x{}, {floor} floor tiles, {boxes} boxes, {zones} zones, {placed} placed, {ice} ice tiles, {teleporters} teleporters, {plates} plates, {doors} doors, {holes} holes{}",
+                level.name,
+                map.width(),
+                map.height(),
+                if state.is_deadlocked() {
+                    ", deadlocked"
+                } else {
+                    ""
+                }
+            );
+        }
+    }
+
+    Ok(read)
+}
@@ -0,0 +1,29 @@
+use std::path::PathBuf;
+
+use crate::{error::CliError, files};
+
+pub fn validate(paths: &[PathBuf]) -> Result<bool, CliError> {
+    let mut valid = true;
+
+    for path in files::expand(paths)? {
+        for level in files::load(&path) {
+            let problems: Vec<String> = match level.state {
+                Ok(state) => state
+                    .validate()
+                    .iter()
+                    .map(|problem| problem.to_string())
+                    .collect(),
+                Err(error) => vec![error.to_string()],
+            };
+
+            if problems.is_empty() {
+                println!("{}: ok", level.name);
+            } else {
+                valid = false;
+                println!("{}: {}", level.name, problems.join("; "));
+            }
+        }
+    }
+
+    Ok(valid)
+}
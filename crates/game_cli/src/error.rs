@@ -0,0 +1,27 @@
+use std::{fmt, io, path::PathBuf};
+
+use game_core::{level::XsbError, solver::SolverError};
+use ron::error::SpannedError;
+
+#[derive(Debug)]
+pub enum CliError {
+    Usage(String),
+    Io(PathBuf, io::Error),
+    Ron(PathBuf, SpannedError),
+    Xsb(XsbError),
+    Solver(SolverError),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage(message) => write!(f, "{message}"),
+            CliError::Io(path, error) => write!(f, "{}: {error}", path.display()),
+            CliError::Ron(path, error) => write!(f, "{}: {error}", path.display()),
+            CliError::Xsb(error) => write!(f, "{error}"),
+            CliError::Solver(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
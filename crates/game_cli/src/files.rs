@@ -0,0 +1,141 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use game_core::level::{LevelPack, LevelState};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use crate::{error::CliError, format::Format};
+
+/// Same layout as the level assets read by the game
+#[derive(Serialize, Deserialize)]
+struct LevelFile(#[serde(with = "game_core::versioned")] LevelPack);
+
+pub struct LoadedLevel {
+    pub name: String,
+    pub path: PathBuf,
+    pub state: Result<LevelState, CliError>,
+}
+
+/// Directories are walked looking for level files
+pub fn expand(paths: &[PathBuf]) -> Result<Vec<PathBuf>, CliError> {
+    let mut files = vec![];
+
+    for path in paths {
+        if path.is_dir() {
+            let mut entries = fs::read_dir(path)
+                .map_err(|error| CliError::Io(path.clone(), error))?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<PathBuf>, _>>()
+                .map_err(|error| CliError::Io(path.clone(), error))?;
+            entries.sort();
+
+            let nested: Vec<PathBuf> = entries
+                .into_iter()
+                .filter(|entry| entry.is_dir() || Format::from_path(entry).is_some())
+                .collect();
+            files.extend(expand(&nested)?);
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    Ok(files)
+}
+
+/// Packs and XSB files can hold many levels, they are named after their number in the file
+pub fn load(path: &Path) -> Vec<LoadedLevel> {
+    let loaded = |name: String, state: Result<LevelState, CliError>| LoadedLevel {
+        name,
+        path: path.to_path_buf(),
+        state,
+    };
+    let name = path.display().to_string();
+
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) => return vec![loaded(name, Err(CliError::Io(path.to_path_buf(), error)))],
+    };
+
+    match Format::from_path(path) {
+        Some(Format::Lvl) => {
+            let pack = match ron::from_str::<LevelFile>(&text) {
+                Ok(LevelFile(pack)) => pack,
+                Err(error) => {
+                    return vec![loaded(name, Err(CliError::Ron(path.to_path_buf(), error)))];
+                }
+            };
+
+            let single = pack.len() == 1;
+            pack.levels()
+                .iter()
+                .enumerate()
+                .map(|(index, level)| {
+                    let name = if single {
+                        name.clone()
+                    } else {
+                        format!("{name}#{}", index + 1)
+                    };
+                    loaded(name, Ok(level.state().clone()))
+                })
+                .collect()
+        }
+        Some(Format::Xsb) => {
+            let levels = LevelState::from_xsb_collection(&text);
+            if levels.is_empty() {
+                return vec![loaded(
+                    name,
+                    LevelState::from_xsb(&text).map_err(CliError::Xsb),
+                )];
+            }
+
+            let single = levels.len() == 1;
+            levels
+                .into_iter()
+                .enumerate()
+                .map(|(index, state)| {
+                    let name = if single {
+                        name.clone()
+                    } else {
+                        format!("{name}#{}", index + 1)
+                    };
+                    loaded(name, state.map_err(CliError::Xsb))
+                })
+                .collect()
+        }
+        None => vec![loaded(
+            name,
+            Err(CliError::Usage(format!(
+                "{} is not a level file",
+                path.display()
+            ))),
+        )],
+    }
+}
+
+pub fn save(path: &Path, state: &LevelState) -> Result<(), CliError> {
+    let text = match Format::from_path(path) {
+        Some(Format::Lvl) => {
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let pack = LevelPack::single(&name, state.clone());
+            let config = PrettyConfig::default().depth_limit(6);
+            let text = ron::ser::to_string_pretty(&LevelFile(pack), config)
+                .map_err(|error| CliError::Io(path.to_path_buf(), io::Error::other(error)))?;
+            text + "\n"
+        }
+        Some(Format::Xsb) => state.to_xsb(),
+        None => {
+            return Err(CliError::Usage(format!(
+                "{} is not a level file",
+                path.display()
+            )));
+        }
+    };
+
+    fs::write(path, text).map_err(|error| CliError::Io(path.to_path_buf(), error))
+}
@@ -0,0 +1,34 @@
+use std::path::Path;
+
+use crate::error::CliError;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Lvl,
+    Xsb,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Option<Format> {
+        match path.extension()?.to_str()? {
+            "lvl" => Some(Format::Lvl),
+            "xsb" | "sok" => Some(Format::Xsb),
+            _ => None,
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Format, CliError> {
+        match name {
+            "lvl" | "ron" => Ok(Format::Lvl),
+            "xsb" | "sok" => Ok(Format::Xsb),
+            _ => Err(CliError::Usage(format!("Unknown format '{name}'"))),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Lvl => "lvl",
+            Format::Xsb => "xsb",
+        }
+    }
+}
@@ -0,0 +1,27 @@
+mod command;
+mod commands;
+mod error;
+mod files;
+mod format;
+
+use std::{env, process::ExitCode};
+
+use command::{Command, USAGE};
+use error::CliError;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match Command::parse(&args).and_then(|command| command.run()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(CliError::Usage(message)) => {
+            eprintln!("{message}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
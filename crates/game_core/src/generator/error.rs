@@ -0,0 +1,26 @@
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GeneratorError {
+    NoBoxes,
+    InvalidSize { cols: usize, rows: usize },
+    TooManyBoxes,
+    NoLevelFound,
+}
+
+impl fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeneratorError::NoBoxes => write!(f, "a level needs at least one box"),
+            GeneratorError::InvalidSize { cols, rows } => {
+                write!(f, "a room of {cols}x{rows} cannot be generated")
+            }
+            GeneratorError::TooManyBoxes => write!(f, "the boxes do not fit in the room"),
+            GeneratorError::NoLevelFound => {
+                write!(f, "no level matching the parameters was found")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GeneratorError {}
@@ -0,0 +1,245 @@
+use crate::{
+    input::Direction,
+    level::{Difficulty, DifficultyLabel, LevelState},
+    map::{Board, MAP_COLS, MAP_ROWS, MAX_MAP_COLS, MAX_MAP_ROWS, Map, MapEntity, MapPosition},
+    solver::Solver,
+};
+
+use super::{GeneratorError, rng::Rng};
+
+const DEFAULT_BOXES: usize = 3;
+const DEFAULT_COLS: usize = 7;
+const DEFAULT_ROWS: usize = 7;
+const DEFAULT_MIN_MOVES: usize = 30;
+const DEFAULT_MAX_ATTEMPTS: usize = 100;
+const MIN_ROOM_SIZE: usize = 3;
+/// One in this many cells of the room is turned into a wall
+const WALL_RATIO: usize = 5;
+/// Cells of the room needed by each box, so they have some space to move
+const CELLS_PER_BOX: usize = 4;
+const SOLVER_MAX_NODES: usize = 100_000;
+
+/// Builds levels by placing the boxes on their zones and pulling them away,
+/// the result is then checked with the solver
+pub struct Generator {
+    seed: u64,
+    boxes: usize,
+    cols: usize,
+    rows: usize,
+    min_moves: usize,
+    min_difficulty: DifficultyLabel,
+    max_attempts: usize,
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Generator {
+        Generator {
+            seed,
+            boxes: DEFAULT_BOXES,
+            cols: DEFAULT_COLS,
+            rows: DEFAULT_ROWS,
+            min_moves: DEFAULT_MIN_MOVES,
+            min_difficulty: DifficultyLabel::Easy,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        }
+    }
+
+    pub fn boxes(mut self, boxes: usize) -> Generator {
+        self.boxes = boxes;
+        self
+    }
+
+    /// Size of the room, without the void around it
+    pub fn size(mut self, cols: usize, rows: usize) -> Generator {
+        self.cols = cols;
+        self.rows = rows;
+        self
+    }
+
+    /// Length of the shortest solution
+    pub fn min_moves(mut self, min_moves: usize) -> Generator {
+        self.min_moves = min_moves;
+        self
+    }
+
+    pub fn min_difficulty(mut self, min_difficulty: DifficultyLabel) -> Generator {
+        self.min_difficulty = min_difficulty;
+        self
+    }
+
+    pub fn max_attempts(mut self, max_attempts: usize) -> Generator {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// The same seed and parameters always give the same level
+    pub fn generate(&self) -> Result<LevelState, GeneratorError> {
+        if self.boxes == 0 {
+            return Err(GeneratorError::NoBoxes);
+        }
+        if self.cols < MIN_ROOM_SIZE
+            || self.rows < MIN_ROOM_SIZE
+            || self.cols + 2 > MAX_MAP_COLS
+            || self.rows + 2 > MAX_MAP_ROWS
+        {
+            return Err(GeneratorError::InvalidSize {
+                cols: self.cols,
+                rows: self.rows,
+            });
+        }
+        if self.boxes * CELLS_PER_BOX > self.cols * self.rows {
+            return Err(GeneratorError::TooManyBoxes);
+        }
+
+        let mut rng = Rng::new(self.seed);
+        let solver = Solver::default().max_nodes(SOLVER_MAX_NODES);
+
+        for _ in 0..self.max_attempts {
+            let Some(state) = self.attempt(&mut rng) else {
+                continue;
+            };
+
+            if solver.solve(&state).is_ok_and(|solution| {
+                solution.moves() >= self.min_moves
+                    && Difficulty::new(&state, &solution).label() >= self.min_difficulty
+            }) {
+                return Ok(state);
+            }
+        }
+
+        Err(GeneratorError::NoLevelFound)
+    }
+
+    fn attempt(&self, rng: &mut Rng) -> Option<LevelState> {
+        let mut map = self.room(rng);
+        let board = Board::new(&map);
+        let open: Vec<usize> = (0..board.cells())
+            .filter(|index| !board.is_wall(*index))
+            .collect();
+        if open.len() < self.boxes * CELLS_PER_BOX {
+            return None;
+        }
+
+        let mut zones = vec![];
+        while zones.len() < self.boxes {
+            let zone = rng.pick(&open);
+            if !zones.contains(&zone) {
+                zones.push(zone);
+            }
+        }
+
+        let mut boxes = zones.clone();
+        let free: Vec<usize> = open
+            .iter()
+            .copied()
+            .filter(|index| !boxes.contains(index))
+            .collect();
+        let mut character = rng.pick(&free);
+
+        for _ in 0..self.boxes * (self.cols + self.rows) * 2 {
+            let reachable = reachable(&board, &boxes, character);
+            let mut pulls = vec![];
+            for (index, box_index) in boxes.iter().enumerate() {
+                for direction in Direction::ALL {
+                    let Some((from, to)) = board.pull_box(*box_index, &direction) else {
+                        continue;
+                    };
+                    if reachable[from] && !boxes.contains(&to) {
+                        pulls.push((index, from, to));
+                    }
+                }
+            }
+
+            if pulls.is_empty() {
+                break;
+            }
+
+            let (index, from, to) = rng.pick(&pulls);
+            boxes[index] = from;
+            character = to;
+        }
+
+        if boxes.iter().all(|index| zones.contains(index)) {
+            return None;
+        }
+
+        let reachable = reachable(&board, &boxes, character);
+        let free: Vec<usize> = open
+            .iter()
+            .copied()
+            .filter(|index| reachable[*index])
+            .collect();
+        let character = rng.pick(&free);
+
+        for index in &open {
+            let entity = match (zones.contains(index), boxes.contains(index)) {
+                (true, true) => MapEntity::P,
+                (true, false) => MapEntity::Z,
+                (false, true) => MapEntity::B,
+                (false, false) => MapEntity::F,
+            };
+            map[&board.position(*index)] = entity;
+        }
+
+        Some(LevelState::new(map, board.position(character)))
+    }
+
+    /// Floor surrounded by void, with walls scattered in it that never split the floor
+    fn room(&self, rng: &mut Rng) -> Map {
+        let width = (self.cols + 2).max(MAP_COLS);
+        let height = (self.rows + 2).max(MAP_ROWS);
+        let offset_x = (width - self.cols) / 2;
+        let offset_y = (height - self.rows) / 2;
+
+        let mut map = Map::new(width, height, MapEntity::V);
+        for position in map.positions().collect::<Vec<_>>() {
+            if (offset_x..offset_x + self.cols).contains(&position.x())
+                && (offset_y..offset_y + self.rows).contains(&position.y())
+            {
+                map[&position] = MapEntity::F;
+            }
+        }
+
+        for _ in 0..self.cols * self.rows / WALL_RATIO {
+            let position = MapPosition::new(
+                offset_x + rng.below(self.cols),
+                offset_y + rng.below(self.rows),
+            );
+            if matches!(map[&position], MapEntity::V) {
+                continue;
+            }
+
+            map[&position] = MapEntity::V;
+            let board = Board::new(&map);
+            let open = (0..board.cells())
+                .filter(|index| !board.is_wall(*index))
+                .collect::<Vec<_>>();
+            let reachable = reachable(&board, &[], open[0]);
+            if open.iter().any(|index| !reachable[*index]) {
+                map[&position] = MapEntity::F;
+            }
+        }
+
+        map
+    }
+}
+
+fn reachable(board: &Board, boxes: &[usize], start: usize) -> Vec<bool> {
+    let mut reached = vec![false; board.cells()];
+    let mut pending = vec![start];
+    reached[start] = true;
+
+    while let Some(index) = pending.pop() {
+        for direction in Direction::ALL {
+            let Some(next) = board.open_step(index, &direction) else {
+                continue;
+            };
+            if !reached[next] && !boxes.contains(&next) {
+                reached[next] = true;
+                pending.push(next);
+            }
+        }
+    }
+
+    reached
+}
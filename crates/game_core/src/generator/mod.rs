@@ -0,0 +1,6 @@
+mod error;
+mod generator;
+mod rng;
+
+pub use error::GeneratorError;
+pub use generator::Generator;
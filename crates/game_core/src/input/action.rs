@@ -1,9 +1,16 @@
 #[derive(Clone, Copy)]
 pub enum Action {
     Undo,
+    Redo,
+    UndoPush,
+    UndoAll,
     Reload,
     Select,
     Toggle,
+    Color,
+    Mode,
+    Export,
+    Import,
     Delete,
     Exit,
 }
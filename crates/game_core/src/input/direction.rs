@@ -1,7 +1,51 @@
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Direction {
     Up,
     Down,
     Left,
     Right,
 }
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    /// Character used by the LURD notation, uppercase when the move pushes a box
+    pub fn to_lurd(&self, push: bool) -> char {
+        let step = match self {
+            Direction::Up => 'u',
+            Direction::Down => 'd',
+            Direction::Left => 'l',
+            Direction::Right => 'r',
+        };
+        if push {
+            step.to_ascii_uppercase()
+        } else {
+            step
+        }
+    }
+
+    pub fn from_lurd(step: char) -> Option<(Direction, bool)> {
+        let direction = match step.to_ascii_lowercase() {
+            'u' => Direction::Up,
+            'd' => Direction::Down,
+            'l' => Direction::Left,
+            'r' => Direction::Right,
+            _ => return None,
+        };
+        Some((direction, step.is_ascii_uppercase()))
+    }
+}
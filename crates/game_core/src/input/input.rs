@@ -27,6 +27,18 @@ impl Input {
         Input::Action(Action::Undo)
     }
 
+    pub fn redo() -> Input {
+        Input::Action(Action::Redo)
+    }
+
+    pub fn undo_push() -> Input {
+        Input::Action(Action::UndoPush)
+    }
+
+    pub fn undo_all() -> Input {
+        Input::Action(Action::UndoAll)
+    }
+
     pub fn reload() -> Input {
         Input::Action(Action::Reload)
     }
@@ -39,6 +51,22 @@ impl Input {
         Input::Action(Action::Toggle)
     }
 
+    pub fn color() -> Input {
+        Input::Action(Action::Color)
+    }
+
+    pub fn mode() -> Input {
+        Input::Action(Action::Mode)
+    }
+
+    pub fn export() -> Input {
+        Input::Action(Action::Export)
+    }
+
+    pub fn import() -> Input {
+        Input::Action(Action::Import)
+    }
+
     pub fn delete() -> Input {
         Input::Action(Action::Delete)
     }
@@ -0,0 +1,412 @@
+use crate::map::{MAX_MAP_COLS, MAX_MAP_ROWS, Map, MapEntity, MapPosition};
+
+use super::{
+    code_error::LevelCodeError,
+    state::{COLORS, LevelState},
+};
+
+/// Codes of the version 1 stored the number of links and colors in a single byte
+const VERSION: u8 = 2;
+
+/// URL safe base64 alphabet, so codes can be pasted anywhere
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Entities are stored by their index in this table, which must only grow
+const ENTITIES: [MapEntity; 13] = [
+    MapEntity::F,
+    MapEntity::Z,
+    MapEntity::B,
+    MapEntity::P,
+    MapEntity::V,
+    MapEntity::I,
+    MapEntity::J,
+    MapEntity::T,
+    MapEntity::U,
+    MapEntity::S,
+    MapEntity::Q,
+    MapEntity::D,
+    MapEntity::H,
+];
+
+/// Longest run of equal entities that fits in a single byte
+const MAX_RUN: usize = 16;
+
+/// The character faces up, down, left or right
+const MAX_FACING_DIRECTION: usize = 3;
+
+/// The version, the size, the character, the entities as runs that keep the length in the
+/// high nibble, the links and colors after their count, and a checksum of all of it
+pub(super) fn encode(state: &LevelState) -> String {
+    let map = state.map();
+    let character = state.character_position();
+    let mut bytes = vec![
+        VERSION,
+        map.width() as u8,
+        map.height() as u8,
+        character.x() as u8,
+        character.y() as u8,
+        state.character_facing_direction() as u8,
+    ];
+
+    let mut entities = map
+        .positions()
+        .map(|position| entity_index(&map[&position]));
+    let mut run = entities.next().map(|entity| (entity, 1));
+    for entity in entities {
+        match run {
+            Some((current, length)) if current == entity && length < MAX_RUN => {
+                run = Some((current, length + 1))
+            }
+            Some((current, length)) => {
+                bytes.push(((length - 1) as u8) << 4 | current);
+                run = Some((entity, 1));
+            }
+            None => unreachable!(),
+        }
+    }
+    if let Some((current, length)) = run {
+        bytes.push(((length - 1) as u8) << 4 | current);
+    }
+
+    for links in [state.teleporters(), state.switches()] {
+        push_count(&mut bytes, links.len());
+        for (from, to) in links {
+            bytes.extend([from.x() as u8, from.y() as u8, to.x() as u8, to.y() as u8]);
+        }
+    }
+
+    let colors = |color: fn(&LevelState, &MapPosition) -> u8| {
+        map.positions()
+            .map(|position| (position, color(state, &position)))
+            .filter(|(_, color)| *color != 0)
+            .collect::<Vec<_>>()
+    };
+    for colors in [
+        colors(LevelState::box_color),
+        colors(LevelState::zone_color),
+    ] {
+        push_count(&mut bytes, colors.len());
+        for (position, color) in colors {
+            bytes.extend([position.x() as u8, position.y() as u8, color]);
+        }
+    }
+
+    bytes.extend(checksum(&bytes));
+    to_base64(&bytes)
+}
+
+/// Whitespace is ignored, so codes split across lines still work
+pub(super) fn decode(code: &str) -> Result<LevelState, LevelCodeError> {
+    let bytes = from_base64(code)?;
+    let Some(split) = bytes.len().checked_sub(2) else {
+        return Err(LevelCodeError::Checksum);
+    };
+    let (bytes, sum) = bytes.split_at(split);
+    if checksum(bytes) != sum {
+        return Err(LevelCodeError::Checksum);
+    }
+
+    let mut reader = Reader { bytes, offset: 0 };
+    let version = reader.next()?;
+    if !(1..=VERSION).contains(&version) {
+        return Err(LevelCodeError::UnsupportedVersion(version));
+    }
+
+    let cols = reader.next()? as usize;
+    let rows = reader.next()? as usize;
+    if cols > MAX_MAP_COLS || rows > MAX_MAP_ROWS {
+        return Err(LevelCodeError::TooLarge { cols, rows });
+    }
+    if cols == 0 || rows == 0 {
+        return Err(LevelCodeError::Malformed);
+    }
+    let character = reader.position()?;
+    let facing_direction = reader.next()? as usize;
+    if facing_direction > MAX_FACING_DIRECTION {
+        return Err(LevelCodeError::Malformed);
+    }
+
+    let mut map = Map::new(cols, rows, MapEntity::F);
+    let mut positions = map.positions().collect::<Vec<_>>().into_iter();
+    while positions.len() > 0 {
+        let run = reader.next()?;
+        let entity = *ENTITIES
+            .get((run & 0x0f) as usize)
+            .ok_or(LevelCodeError::Malformed)?;
+        for _ in 0..=run >> 4 {
+            let position = positions.next().ok_or(LevelCodeError::Malformed)?;
+            map[&position] = entity;
+        }
+    }
+
+    let contains = |position: &MapPosition| map.contains(position);
+    if !contains(&character) {
+        return Err(LevelCodeError::Malformed);
+    }
+    let mut links = [vec![], vec![]];
+    for links in &mut links {
+        for _ in 0..reader.count(version)? {
+            let (from, to) = (reader.position()?, reader.position()?);
+            if !contains(&from) || !contains(&to) {
+                return Err(LevelCodeError::Malformed);
+            }
+            links.push((from, to));
+        }
+    }
+    let mut colors = [vec![], vec![]];
+    for colors in &mut colors {
+        for _ in 0..reader.count(version)? {
+            let position = reader.position()?;
+            if !contains(&position) {
+                return Err(LevelCodeError::Malformed);
+            }
+            let color = reader.next()?;
+            if color >= COLORS {
+                return Err(LevelCodeError::Malformed);
+            }
+            colors.push((position, color));
+        }
+    }
+    if reader.offset != bytes.len() {
+        return Err(LevelCodeError::Malformed);
+    }
+
+    let mut state = LevelState::new(map, character);
+    state.set_character_facing_direction(facing_direction);
+    let [teleporters, switches] = links;
+    for (pad, partner) in teleporters {
+        state.link_teleporters(pad, partner);
+    }
+    for (plate, door) in switches {
+        state.link_switch(plate, door);
+    }
+    let [box_colors, zone_colors] = colors;
+    for (position, color) in box_colors {
+        state.set_box_color(position, color);
+    }
+    for (position, color) in zone_colors {
+        state.set_zone_color(position, color);
+    }
+    Ok(state)
+}
+
+/// Seven bits per byte, the high bit is set while more bytes follow
+fn push_count(bytes: &mut Vec<u8>, mut count: usize) {
+    while count >= 0x80 {
+        bytes.push((count & 0x7f) as u8 | 0x80);
+        count >>= 7;
+    }
+    bytes.push(count as u8);
+}
+
+fn entity_index(entity: &MapEntity) -> u8 {
+    ENTITIES
+        .iter()
+        .position(|known| known == entity)
+        .unwrap_or_default() as u8
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn next(&mut self) -> Result<u8, LevelCodeError> {
+        let byte = self
+            .bytes
+            .get(self.offset)
+            .ok_or(LevelCodeError::Malformed)?;
+        self.offset += 1;
+        Ok(*byte)
+    }
+
+    fn count(&mut self, version: u8) -> Result<usize, LevelCodeError> {
+        if version == 1 {
+            return self.next().map(usize::from);
+        }
+
+        let mut count = 0;
+        // a map cannot hold more than three bytes worth of links or colors
+        for shift in [0, 7, 14] {
+            let byte = self.next()?;
+            count |= usize::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(count);
+            }
+        }
+        Err(LevelCodeError::Malformed)
+    }
+
+    fn position(&mut self) -> Result<MapPosition, LevelCodeError> {
+        Ok(MapPosition::new(
+            self.next()? as usize,
+            self.next()? as usize,
+        ))
+    }
+}
+
+/// Fletcher-16, enough to catch a mistyped or cut code
+fn checksum(bytes: &[u8]) -> [u8; 2] {
+    let (mut low, mut high) = (0u16, 0u16);
+    for byte in bytes {
+        low = (low + *byte as u16) % 255;
+        high = (high + low) % 255;
+    }
+    [high as u8, low as u8]
+}
+
+fn to_base64(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let value = chunk.iter().enumerate().fold(0u32, |value, (i, byte)| {
+            value | (*byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..=chunk.len() {
+            text.push(ALPHABET[(value >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    text
+}
+
+fn from_base64(text: &str) -> Result<Vec<u8>, LevelCodeError> {
+    let digits = text
+        .chars()
+        .filter(|character| !character.is_whitespace())
+        .map(|character| {
+            ALPHABET
+                .iter()
+                .position(|known| *known as char == character)
+                .map(|digit| digit as u32)
+                .ok_or(LevelCodeError::InvalidCharacter(character))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if digits.is_empty() {
+        return Err(LevelCodeError::Empty);
+    }
+    // a lone digit can not hold a byte
+    if digits.len() % 4 == 1 {
+        return Err(LevelCodeError::Checksum);
+    }
+
+    let mut bytes = Vec::with_capacity(digits.len() * 3 / 4);
+    for chunk in digits.chunks(4) {
+        let value = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |value, (i, digit)| value | digit << (18 - 6 * i));
+        for i in 0..chunk.len() - 1 {
+            bytes.push((value >> (16 - 8 * i)) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEVEL: &str = "########\n#  1   #\n# ~~ ^ #\n#@$ $.*#\n#  1   #\n#      #\n########\n";
+
+    fn assert_same_level(left: &LevelState, right: &LevelState) {
+        assert_eq!(left.to_xsb(), right.to_xsb());
+        assert_eq!(left.character_position(), right.character_position());
+        assert_eq!(
+            left.character_facing_direction(),
+            right.character_facing_direction()
+        );
+        assert_eq!(left.remaining_zones(), right.remaining_zones());
+        assert_eq!(left.teleporters(), right.teleporters());
+        assert_eq!(left.switches(), right.switches());
+        for position in left.map().positions() {
+            assert_eq!(left.box_color(&position), right.box_color(&position));
+            assert_eq!(left.zone_color(&position), right.zone_color(&position));
+        }
+    }
+
+    fn colored_level() -> LevelState {
+        let mut state = LevelState::from_xsb(LEVEL).unwrap();
+        let character = state.character_position();
+        state.set_box_color(MapPosition::new(character.x() + 1, character.y()), 1);
+        state.set_zone_color(MapPosition::new(character.x() + 4, character.y()), 1);
+        state
+    }
+
+    /// Changes a byte of a valid code and signs it again, as a forged code would be
+    fn forge(code: &str, index: impl Fn(usize) -> usize, byte: u8) -> String {
+        let mut bytes = from_base64(code).unwrap();
+        bytes.truncate(bytes.len() - 2);
+        let index = index(bytes.len());
+        bytes[index] = byte;
+        bytes.extend(checksum(&bytes));
+        to_base64(&bytes)
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut state = colored_level();
+        state.set_character_facing_direction(3);
+        let code = state.to_code();
+        assert_same_level(&state, &LevelState::from_code(&code).unwrap());
+
+        let (head, tail) = code.split_at(code.len() / 2);
+        let spaced = format!(" {head}\n{tail} ");
+        assert_same_level(&state, &LevelState::from_code(&spaced).unwrap());
+    }
+
+    #[test]
+    fn round_trip_more_than_255_colors() {
+        let rows = format!("#{}#\n", "$".repeat(MAX_MAP_COLS - 3)).repeat(MAX_MAP_ROWS - 2);
+        let wall = "#".repeat(MAX_MAP_COLS - 1);
+        let text = format!("{wall}\n{}{wall}\n", rows.replacen('$', "@", 1));
+        let mut state = LevelState::from_xsb(&text).unwrap();
+        let boxes = state
+            .map()
+            .positions()
+            .filter(|position| state.get_entity(position).has_box())
+            .collect::<Vec<_>>();
+        assert!(boxes.len() > 255);
+        for (i, position) in boxes.into_iter().enumerate() {
+            state.set_box_color(position, (i % COLORS as usize) as u8);
+        }
+
+        assert_same_level(&state, &LevelState::from_code(&state.to_code()).unwrap());
+    }
+
+    #[test]
+    fn decode_version_1() {
+        let code = "AQoKAgQA9FQQByA0ABUADAA0AAIAAgEDNBAHIDRQ9PQBBAIEBQABAwQBAQYEAS4s";
+        assert_same_level(&colored_level(), &LevelState::from_code(code).unwrap());
+    }
+
+    #[test]
+    fn reject_broken_codes() {
+        let code = colored_level().to_code();
+        assert_eq!(LevelState::from_code("").err(), Some(LevelCodeError::Empty));
+        assert_eq!(
+            LevelState::from_code("ab*c").err(),
+            Some(LevelCodeError::InvalidCharacter('*'))
+        );
+        assert_eq!(
+            LevelState::from_code(&code[..code.len() - 4]).err(),
+            Some(LevelCodeError::Checksum)
+        );
+    }
+
+    #[test]
+    fn reject_out_of_range_values() {
+        let code = colored_level().to_code();
+        // the version, the size and the character position come before the facing direction
+        let facing = forge(&code, |_| 5, 200);
+        assert_eq!(
+            LevelState::from_code(&facing).err(),
+            Some(LevelCodeError::Malformed)
+        );
+        // the zone color is the last byte before the checksum
+        let color = forge(&code, |len| len - 1, COLORS);
+        assert_eq!(
+            LevelState::from_code(&color).err(),
+            Some(LevelCodeError::Malformed)
+        );
+    }
+}
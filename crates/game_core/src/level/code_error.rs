@@ -0,0 +1,35 @@
+use std::fmt;
+
+use crate::map::{MAX_MAP_COLS, MAX_MAP_ROWS};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LevelCodeError {
+    Empty,
+    InvalidCharacter(char),
+    UnsupportedVersion(u8),
+    Checksum,
+    Malformed,
+    TooLarge { cols: usize, rows: usize },
+}
+
+impl fmt::Display for LevelCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelCodeError::Empty => write!(f, "the code is empty"),
+            LevelCodeError::InvalidCharacter(character) => {
+                write!(f, "the character '{character}' is not part of a level code")
+            }
+            LevelCodeError::UnsupportedVersion(version) => {
+                write!(f, "the code version {version} is not supported")
+            }
+            LevelCodeError::Checksum => write!(f, "the code is mistyped or incomplete"),
+            LevelCodeError::Malformed => write!(f, "the code does not describe a level"),
+            LevelCodeError::TooLarge { cols, rows } => write!(
+                f,
+                "the level is {cols}x{rows}, larger than the {MAX_MAP_COLS}x{MAX_MAP_ROWS} limit"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LevelCodeError {}
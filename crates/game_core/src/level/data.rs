@@ -11,7 +11,7 @@ pub struct LevelData {
 impl From<LevelState> for LevelData {
     fn from(value: LevelState) -> Self {
         LevelData {
-            state: value,
+            state: value.clone(),
             initial_state: value,
         }
     }
@@ -33,6 +33,6 @@ impl DerefMut for LevelData {
 
 impl LevelData {
     pub fn reload(&mut self) {
-        self.state = self.initial_state;
+        self.state = self.initial_state.clone();
     }
 }
@@ -0,0 +1,131 @@
+use crate::{
+    input::Direction,
+    map::{Board, MapPosition},
+};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Deadlock {
+    /// Box outside a zone stuck in a corner
+    Corner(MapPosition),
+    /// Box outside a zone pinned against a wall with no zone along it
+    Wall(MapPosition),
+    /// Boxes blocking each other in a 2x2 square, not all of them in zones
+    Frozen(Vec<MapPosition>),
+}
+
+/// Levels with ice or teleporters are left out, a box does not stop where it is pushed there
+pub(crate) fn find_deadlocks(board: &Board, boxes: &[usize]) -> Vec<Deadlock> {
+    if !board.is_classic() {
+        return vec![];
+    }
+
+    let spare = boxes.len().saturating_sub(board.zones().count());
+    let mut deadlocks = vec![];
+    let mut stuck = vec![];
+
+    for index in boxes.iter().filter(|index| !board.is_zone(**index)) {
+        if is_corner(board, *index) {
+            deadlocks.push(Deadlock::Corner(board.position(*index)));
+            stuck.push(*index);
+        } else if is_against_dead_wall(board, *index) {
+            deadlocks.push(Deadlock::Wall(board.position(*index)));
+            stuck.push(*index);
+        }
+    }
+
+    let mut squares: Vec<Vec<usize>> = vec![];
+    for index in boxes {
+        for square in frozen_squares(board, boxes, *index) {
+            if square.len() > 1 && !squares.contains(&square) {
+                stuck.extend(square.iter().filter(|index| !board.is_zone(**index)));
+                deadlocks.push(Deadlock::Frozen(
+                    square.iter().map(|index| board.position(*index)).collect(),
+                ));
+                squares.push(square);
+            }
+        }
+    }
+
+    stuck.sort_unstable();
+    stuck.dedup();
+
+    // Extra boxes can be left behind, as long as enough of them can still move
+    if stuck.len() > spare {
+        deadlocks
+    } else {
+        vec![]
+    }
+}
+
+/// Returns the boxes of every frozen 2x2 square that contains the given box
+pub(crate) fn frozen_squares(
+    board: &Board,
+    boxes: &[usize],
+    index: usize,
+) -> impl Iterator<Item = Vec<usize>> {
+    let is_blocked = move |cell: Option<usize>| {
+        cell.is_none_or(|cell| board.is_wall(cell) || boxes.binary_search(&cell).is_ok())
+    };
+
+    [
+        (Direction::Left, Direction::Up),
+        (Direction::Right, Direction::Up),
+        (Direction::Left, Direction::Down),
+        (Direction::Right, Direction::Down),
+    ]
+    .into_iter()
+    .filter_map(move |(horizontal, vertical)| {
+        let beside = board.step(index, &horizontal);
+        let cells = [
+            Some(index),
+            beside,
+            board.step(index, &vertical),
+            beside.and_then(|beside| board.step(beside, &vertical)),
+        ];
+        if !cells.into_iter().all(is_blocked) {
+            return None;
+        }
+
+        let mut square: Vec<usize> = cells
+            .into_iter()
+            .flatten()
+            .filter(|cell| boxes.binary_search(cell).is_ok())
+            .collect();
+        square.sort_unstable();
+        square
+            .iter()
+            .any(|cell| !board.is_zone(*cell))
+            .then_some(square)
+    })
+}
+
+fn is_corner(board: &Board, index: usize) -> bool {
+    let blocked = |direction: Direction| board.open_step(index, &direction).is_none();
+    (blocked(Direction::Up) || blocked(Direction::Down))
+        && (blocked(Direction::Left) || blocked(Direction::Right))
+}
+
+fn is_against_dead_wall(board: &Board, index: usize) -> bool {
+    [
+        (Direction::Up, [Direction::Left, Direction::Right]),
+        (Direction::Down, [Direction::Left, Direction::Right]),
+        (Direction::Left, [Direction::Up, Direction::Down]),
+        (Direction::Right, [Direction::Up, Direction::Down]),
+    ]
+    .into_iter()
+    .any(|(wall, sides)| {
+        board.open_step(index, &wall).is_none()
+            && sides.iter().all(|side| {
+                let mut current = index;
+                loop {
+                    if board.is_zone(current) || board.open_step(current, &wall).is_some() {
+                        return false;
+                    }
+                    match board.open_step(current, side) {
+                        Some(next) => current = next,
+                        None => return true,
+                    }
+                }
+            })
+    })
+}
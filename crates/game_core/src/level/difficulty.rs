@@ -0,0 +1,97 @@
+use std::fmt;
+
+use crate::solver::Solution;
+
+use super::state::LevelState;
+
+const MEDIUM_SCORE: usize = 60;
+const HARD_SCORE: usize = 100;
+const EXPERT_SCORE: usize = 160;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum DifficultyLabel {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+}
+
+impl DifficultyLabel {
+    pub fn from_score(score: usize) -> DifficultyLabel {
+        match score {
+            score if score >= EXPERT_SCORE => DifficultyLabel::Expert,
+            score if score >= HARD_SCORE => DifficultyLabel::Hard,
+            score if score >= MEDIUM_SCORE => DifficultyLabel::Medium,
+            _ => DifficultyLabel::Easy,
+        }
+    }
+}
+
+impl fmt::Display for DifficultyLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DifficultyLabel::Easy => write!(f, "Easy"),
+            DifficultyLabel::Medium => write!(f, "Medium"),
+            DifficultyLabel::Hard => write!(f, "Hard"),
+            DifficultyLabel::Expert => write!(f, "Expert"),
+        }
+    }
+}
+
+/// Metrics of a level and of its optimal solution
+#[derive(Clone, Copy, Debug)]
+pub struct Difficulty {
+    moves: usize,
+    pushes: usize,
+    branching_factor: f64,
+    dead_squares: usize,
+    boxes: usize,
+}
+
+impl Difficulty {
+    pub fn new(state: &LevelState, solution: &Solution) -> Difficulty {
+        let board = state.board();
+
+        Difficulty {
+            moves: solution.moves(),
+            pushes: solution.pushes(),
+            branching_factor: solution.branching_factor(),
+            dead_squares: board.dead_squares().count(),
+            boxes: board.boxes(state.map()).len(),
+        }
+    }
+
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    pub fn pushes(&self) -> usize {
+        self.pushes
+    }
+
+    pub fn branching_factor(&self) -> f64 {
+        self.branching_factor
+    }
+
+    pub fn dead_squares(&self) -> usize {
+        self.dead_squares
+    }
+
+    pub fn boxes(&self) -> usize {
+        self.boxes
+    }
+
+    /// Pushes weigh the most, walking around matters less than having many
+    /// choices, many boxes and many places to get them stuck
+    pub fn score(&self) -> usize {
+        self.pushes * 3
+            + self.moves / 4
+            + (self.branching_factor * 10.0) as usize
+            + self.dead_squares
+            + self.boxes * 5
+    }
+
+    pub fn label(&self) -> DifficultyLabel {
+        DifficultyLabel::from_score(self.score())
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::VecDeque;
+
+use super::{state::LevelState, undo_policy::UndoPolicy};
+
+#[derive(Clone)]
+struct LevelSnapshot {
+    state: LevelState,
+    step: char,
+}
+
+#[derive(Default)]
+pub struct LevelHistory {
+    policy: UndoPolicy,
+    used_undos: usize,
+    past: VecDeque<LevelSnapshot>,
+    future: Vec<LevelSnapshot>,
+}
+
+impl LevelHistory {
+    pub fn new(policy: UndoPolicy) -> LevelHistory {
+        LevelHistory {
+            policy,
+            ..LevelHistory::default()
+        }
+    }
+
+    pub fn policy(&self) -> UndoPolicy {
+        self.policy
+    }
+
+    pub fn reset(&mut self) {
+        *self = LevelHistory::new(self.policy);
+    }
+
+    pub fn remaining_undos(&self) -> Option<usize> {
+        match self.policy {
+            UndoPolicy::Limited(undos) => Some(undos.saturating_sub(self.used_undos)),
+            UndoPolicy::Unlimited => None,
+        }
+    }
+
+    pub fn undos_string(&self) -> String {
+        match self.remaining_undos() {
+            Some(undos) => undos.to_string(),
+            None => UndoPolicy::Unlimited.to_string(),
+        }
+    }
+
+    pub fn save(&mut self, state: &LevelState, step: char) {
+        self.future.clear();
+        self.past.push_back(LevelSnapshot {
+            state: state.clone(),
+            step,
+        });
+
+        if let Some(undos) = self.remaining_undos() {
+            while self.past.len() > undos {
+                self.past.pop_front();
+            }
+        }
+    }
+
+    /// Returns the previous state and the LURD step of the undone move
+    pub fn undo(&mut self, current: &LevelState) -> Option<(LevelState, char)> {
+        if self.remaining_undos() == Some(0) {
+            return None;
+        }
+
+        let snapshot = self.past.pop_back()?;
+        self.future.push(LevelSnapshot {
+            state: current.clone(),
+            step: snapshot.step,
+        });
+        self.used_undos += 1;
+        Some((snapshot.state, snapshot.step))
+    }
+
+    pub fn redo(&mut self, current: &LevelState) -> Option<(LevelState, char)> {
+        let snapshot = self.future.pop()?;
+        self.past.push_back(LevelSnapshot {
+            state: current.clone(),
+            step: snapshot.step,
+        });
+        Some((snapshot.state, snapshot.step))
+    }
+}
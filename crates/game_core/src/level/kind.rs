@@ -4,7 +4,11 @@ use super::state::LevelState;
 pub enum LevelKind {
     Stock(usize),
     Custom(String),
+    /// Level of a pack, by the key of the pack and the index of the level in it
+    Pack(String, usize),
     Editable(LevelState),
+    /// Made by the generator from this seed
+    Generated(u64),
 }
 
 impl Default for LevelKind {
@@ -2,18 +2,22 @@ use std::time::Duration;
 
 use crate::{
     input::{Action, Direction, Input},
-    level::{LevelUpdate, data::LevelData},
-    map::{MAP_COLS, MAP_ROWS, MapEntity, MapPosition},
+    level::{Deadlock, LevelUpdate, PlayMode, RecordRanking, data::LevelData},
+    map::{MapEntity, MapPosition},
 };
 
-use super::{kind::LevelKind, record::LevelRecord, snapshots::LevelSnapshots, state::LevelState};
+use super::{
+    history::LevelHistory, kind::LevelKind, path::find_path, record::LevelRecord,
+    state::LevelState, undo_policy::UndoPolicy,
+};
 
 #[derive(Default)]
 pub struct Level {
     kind: LevelKind,
     state: LevelData,
+    mode: PlayMode,
     record: LevelRecord,
-    snapshots: LevelSnapshots,
+    history: LevelHistory,
 }
 
 impl Level {
@@ -25,6 +29,23 @@ impl Level {
         }
     }
 
+    pub fn with_undo_policy(mut self, undo_policy: UndoPolicy) -> Level {
+        self.history = LevelHistory::new(undo_policy);
+        self.record.set_undo_policy(undo_policy);
+        self
+    }
+
+    /// Levels that cannot be played backwards keep the push mode
+    pub fn with_mode(mut self, mode: PlayMode) -> Level {
+        if mode.is_pull()
+            && let Some(state) = self.state.reversed()
+        {
+            self.state = state.into();
+            self.mode = mode;
+        }
+        self
+    }
+
     pub fn update(&mut self, input: &Input) -> Option<LevelUpdate> {
         match input {
             Input::Direction(direction) => self.handle_direction_input(direction),
@@ -40,75 +61,219 @@ impl Level {
             Direction::Right => self.set_character_facing_direction(3),
         }
 
+        let open_doors = self.state.open_doors();
+        let update = self.step(direction)?;
+        if matches!(update, LevelUpdate::PlaceBox) {
+            return Some(update);
+        }
+
+        let now_open_doors = self.state.open_doors();
+        if now_open_doors.iter().any(|door| !open_doors.contains(door)) {
+            Some(LevelUpdate::DoorOpened)
+        } else if open_doors.iter().any(|door| !now_open_doors.contains(door)) {
+            Some(LevelUpdate::DoorClosed)
+        } else {
+            Some(update)
+        }
+    }
+
+    fn step(&mut self, direction: &Direction) -> Option<LevelUpdate> {
+        if self.mode.is_pull() {
+            return self.pull(direction);
+        }
+
         let mut next_position = self.character_position();
-        next_position.update(direction);
-
-        let next_entity = self.get_entity(&next_position);
-        match next_entity {
-            MapEntity::V => None,
-            MapEntity::B | MapEntity::P => {
-                let in_zone = matches!(next_entity, MapEntity::P);
-                let updated_next_entity = if in_zone { MapEntity::Z } else { MapEntity::F };
-
-                let mut adjacent_position = next_position;
-                adjacent_position.update(direction);
-
-                let adjacent_entity = self.get_entity(&adjacent_position);
-                match adjacent_entity {
-                    MapEntity::F => {
-                        self.snapshots.save(&self.state);
-                        self.set_entity(&next_position, updated_next_entity);
-                        self.set_entity(&adjacent_position, MapEntity::B);
-                        self.move_character(next_position);
-                        self.record.increment_moves();
-
-                        if in_zone {
-                            self.state.increment_remaining_zones();
-                        }
-
-                        Some(LevelUpdate::PushBox)
-                    }
-                    MapEntity::Z => {
-                        self.snapshots.save(&self.state);
-                        self.set_entity(&next_position, updated_next_entity);
-                        self.set_entity(&adjacent_position, MapEntity::P);
-                        self.move_character(next_position);
-                        self.record.increment_moves();
-
-                        if !in_zone {
-                            self.state.decrement_remaining_zones();
-                        }
-
-                        Some(LevelUpdate::PlaceBox)
-                    }
-                    _ => None,
-                }
+        next_position.update(direction, self.state.map());
+
+        let next_entity = *self.get_entity(&next_position);
+        if matches!(next_entity, MapEntity::V | MapEntity::H)
+            || matches!(next_entity, MapEntity::D) && !self.state.is_door_open(&next_position)
+        {
+            return None;
+        }
+
+        if !next_entity.has_box() {
+            self.history.save(&self.state, direction.to_lurd(false));
+            self.move_character(next_position);
+            let slid = self.slide_character(direction);
+            let teleported = self.teleport_character();
+            self.record.push_move(direction.to_lurd(false));
+
+            return Some(if teleported {
+                LevelUpdate::TeleportCharacter
+            } else if slid {
+                LevelUpdate::SlideCharacter
+            } else {
+                LevelUpdate::MoveCharacter
+            });
+        }
+
+        let mut box_position = next_position;
+        box_position.update(direction, self.state.map());
+        let fills_hole = matches!(self.get_entity(&box_position), MapEntity::H);
+        if box_position == next_position || !fills_hole && !self.get_entity(&box_position).is_free()
+        {
+            return None;
+        }
+
+        let mut slid = false;
+        while !fills_hole && self.get_entity(&box_position).is_ice() {
+            let mut slide_position = box_position;
+            slide_position.update(direction, self.state.map());
+            if slide_position == box_position || !self.get_entity(&slide_position).is_free() {
+                break;
             }
-            _ => {
-                self.snapshots.save(&self.state);
-                self.move_character(next_position);
-                self.record.increment_moves();
+            box_position = slide_position;
+            slid = true;
+        }
+
+        // the box is still where the character is moving into, so it never comes back there
+        let mut teleported = false;
+        if !fills_hole
+            && let Some(partner) = self.state.teleporter_partner(&box_position)
+            && self.get_entity(&partner).is_free()
+        {
+            box_position = partner;
+            teleported = true;
+        }
+
+        let box_entity = *self.get_entity(&box_position);
+        let was_placed = self.state.is_placed(&next_position);
+        self.history.save(&self.state, direction.to_lurd(true));
+        self.set_entity(&next_position, next_entity.without_box());
+        self.state.move_box_color(&next_position, box_position);
+        if fills_hole {
+            self.set_entity(&box_position, MapEntity::F);
+            self.state.set_box_color(box_position, 0);
+        } else {
+            self.set_entity(&box_position, box_entity.with_box());
+        }
+        self.move_character(next_position);
+        self.slide_character(direction);
+        self.teleport_character();
+        self.record.push_move(direction.to_lurd(true));
+
+        if was_placed {
+            self.state.increment_remaining_zones();
+        }
+
+        if self.state.is_placed(&box_position) {
+            self.state.decrement_remaining_zones();
+            Some(LevelUpdate::PlaceBox)
+        } else if fills_hole {
+            Some(LevelUpdate::FillHole)
+        } else if teleported {
+            Some(LevelUpdate::TeleportBox)
+        } else if slid {
+            Some(LevelUpdate::SlideBox)
+        } else {
+            Some(LevelUpdate::PushBox)
+        }
+    }
+
+    /// Walks away from the box behind the character, which follows it,
+    /// the reversed levels only have the classic tiles
+    fn pull(&mut self, direction: &Direction) -> Option<LevelUpdate> {
+        let board = self.state.board();
+        let character = board.index(&self.character_position());
+        let behind = board
+            .open_step(character, &direction.opposite())
+            .filter(|index| self.get_entity(&board.position(*index)).has_box());
+
+        let Some(box_index) = behind else {
+            let next_position = board.position(board.open_step(character, direction)?);
+            if !self.get_entity(&next_position).is_free() {
+                return None;
+            }
+            self.history.save(&self.state, direction.to_lurd(false));
+            self.move_character(next_position);
+            self.record.push_move(direction.to_lurd(false));
+            return Some(LevelUpdate::MoveCharacter);
+        };
+
+        let (from, to) = board.pull_box(box_index, direction)?;
+        let (box_position, from, to) = (
+            board.position(box_index),
+            board.position(from),
+            board.position(to),
+        );
+        if !self.get_entity(&to).is_free() {
+            return None;
+        }
+
+        let was_placed = self.state.is_placed(&box_position);
+        self.history.save(&self.state, direction.to_lurd(true));
+        self.set_entity(&box_position, self.get_entity(&box_position).without_box());
+        self.state.move_box_color(&box_position, from);
+        self.set_entity(&from, self.get_entity(&from).with_box());
+        self.move_character(to);
+        self.record.push_move(direction.to_lurd(true));
+
+        if was_placed {
+            self.state.increment_remaining_zones();
+        }
+
+        if self.state.is_placed(&from) {
+            self.state.decrement_remaining_zones();
+            Some(LevelUpdate::PlaceBox)
+        } else {
+            Some(LevelUpdate::PullBox)
+        }
+    }
+
+    /// Returns whether the character slid over the ice
+    fn slide_character(&mut self, direction: &Direction) -> bool {
+        let mut slid = false;
+        let mut position = self.character_position();
+        while self.get_entity(&position).is_ice() {
+            let mut slide_position = position;
+            slide_position.update(direction, self.state.map());
+            if slide_position == position || !self.get_entity(&slide_position).is_free() {
+                break;
+            }
+            position = slide_position;
+            slid = true;
+        }
+        self.move_character(position);
+        slid
+    }
 
-                Some(LevelUpdate::MoveCharacter)
+    /// Returns whether the character went through a teleporter
+    fn teleport_character(&mut self) -> bool {
+        match self.state.teleporter_partner(&self.character_position()) {
+            Some(partner) if self.get_entity(&partner).is_free() => {
+                self.move_character(partner);
+                true
             }
+            _ => false,
         }
     }
 
     fn handle_action_input(&mut self, action: &Action) -> Option<LevelUpdate> {
         match action {
-            Action::Undo => {
-                if let Some(state) = self.snapshots.shift() {
-                    *self.state = state;
-                    self.record.decrement_moves();
-                    Some(LevelUpdate::UndoMove)
-                } else {
-                    None
+            Action::Undo => self.undo().map(|_| LevelUpdate::UndoMove),
+            Action::Redo => self.redo().map(|_| LevelUpdate::RedoMove),
+            Action::UndoPush => {
+                let mut undone = false;
+                while let Some(push) = self.undo() {
+                    undone = true;
+                    if push {
+                        break;
+                    }
+                }
+                undone.then_some(LevelUpdate::UndoMove)
+            }
+            Action::UndoAll => {
+                let mut undone = false;
+                while self.undo().is_some() {
+                    undone = true;
                 }
+                undone.then_some(LevelUpdate::UndoMove)
             }
             Action::Reload => {
                 self.state.reload();
                 self.record.reset_moves();
-                self.snapshots.reset();
+                self.history.reset();
                 Some(LevelUpdate::Reload)
             }
             Action::Exit => Some(LevelUpdate::Exit),
@@ -116,22 +281,76 @@ impl Level {
         }
     }
 
+    /// Returns whether the undone move was a push
+    fn undo(&mut self) -> Option<bool> {
+        let (state, step) = self.history.undo(&self.state)?;
+        *self.state = state;
+        self.record.pop_move();
+        Some(step.is_ascii_uppercase())
+    }
+
+    fn redo(&mut self) -> Option<bool> {
+        let (state, step) = self.history.redo(&self.state)?;
+        *self.state = state;
+        self.record.push_move(step);
+        Some(step.is_ascii_uppercase())
+    }
+
+    /// Directions that walk the character to the target without moving any box,
+    /// each of them is meant to go through `update` so the moves are recorded
+    pub fn path_to(&self, target: &MapPosition) -> Option<Vec<Direction>> {
+        find_path(&self.state, target, self.mode.is_pull())
+    }
+
+    /// Same as `path_to` for taking a box to the target, boxes cannot be pushed in pull mode
+    pub fn push_path(
+        &self,
+        box_position: &MapPosition,
+        target: &MapPosition,
+    ) -> Option<Vec<Direction>> {
+        if self.mode.is_pull() {
+            return None;
+        }
+        self.state.push_path(box_position, target)
+    }
+
+    /// Plays a LURD move log, failing if a step cannot be made or its case
+    /// does not match whether it pushes a box
+    pub fn replay(&mut self, lurd: &str) -> bool {
+        for step in lurd.chars() {
+            let Some((direction, _)) = Direction::from_lurd(step) else {
+                return false;
+            };
+            if self.update(&Input::Direction(direction)).is_none()
+                || !self.record.lurd().ends_with(step)
+            {
+                return false;
+            }
+        }
+        true
+    }
+
     pub fn kind(&self) -> &LevelKind {
         &self.kind
     }
 
+    pub fn mode(&self) -> PlayMode {
+        self.mode
+    }
+
     pub fn is_stock(&self) -> bool {
         matches!(self.kind, LevelKind::Stock(_))
     }
 
     pub fn name(&self) -> String {
         match &self.kind {
-            LevelKind::Stock(index) => (index + 1).to_string(),
+            LevelKind::Stock(index) | LevelKind::Pack(_, index) => (index + 1).to_string(),
             LevelKind::Custom(key) => {
                 let parsed_key: Vec<&str> = key.split('$').collect();
                 parsed_key[0].to_string()
             }
             LevelKind::Editable(_) => "Playtest".to_string(),
+            LevelKind::Generated(seed) => format!("#{seed:x}"),
         }
     }
 
@@ -140,12 +359,9 @@ impl Level {
     where
         F: FnMut(&MapEntity, MapPosition),
     {
-        for column in 0..MAP_COLS {
-            for row in 0..MAP_ROWS {
-                let position = MapPosition::new(column, row);
-                let entity = self.state.get_entity(&position);
-                f(entity, position);
-            }
+        for position in self.state.map().positions() {
+            let entity = self.state.get_entity(&position);
+            f(entity, position);
         }
     }
 
@@ -169,6 +385,38 @@ impl Level {
         self.state.move_character(position);
     }
 
+    pub fn teleporter_partner(&self, position: &MapPosition) -> Option<MapPosition> {
+        self.state.teleporter_partner(position)
+    }
+
+    pub fn link_teleporters(&mut self, pad: MapPosition, partner: MapPosition) {
+        self.state.link_teleporters(pad, partner);
+    }
+
+    pub fn unlink_teleporter(&mut self, position: &MapPosition) {
+        self.state.unlink_teleporter(position);
+    }
+
+    pub fn set_box_color(&mut self, position: MapPosition, color: u8) {
+        self.state.set_box_color(position, color);
+    }
+
+    pub fn set_zone_color(&mut self, position: MapPosition, color: u8) {
+        self.state.set_zone_color(position, color);
+    }
+
+    pub fn link_switch(&mut self, plate: MapPosition, door: MapPosition) {
+        self.state.link_switch(plate, door);
+    }
+
+    pub fn unlink_switch(&mut self, position: &MapPosition) {
+        self.state.unlink_switch(position);
+    }
+
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.state.resize(width, height);
+    }
+
     // TODO: This can be an actual direction
     pub fn character_facing_direction(&self) -> usize {
         self.state.character_facing_direction()
@@ -190,22 +438,46 @@ impl Level {
         self.state.no_remaining_zones()
     }
 
+    /// Pulling can take the boxes out of any corner, so only pushes get stuck
+    pub fn deadlocks(&self) -> Vec<Deadlock> {
+        if self.mode.is_pull() {
+            return vec![];
+        }
+        self.state.deadlocks()
+    }
+
+    pub fn is_deadlocked(&self) -> bool {
+        !self.deadlocks().is_empty()
+    }
+
     pub fn undos_string(&self) -> String {
-        self.snapshots.undos_string()
+        self.history.undos_string()
+    }
+
+    pub fn undo_policy(&self) -> UndoPolicy {
+        self.history.policy()
     }
 
     pub fn record(&self) -> &LevelRecord {
         &self.record
     }
 
-    pub fn is_new_record(&self, other: &LevelRecord) -> bool {
-        self.record.is_better_than(other)
+    pub fn is_new_record(&self, other: &LevelRecord, ranking: RecordRanking) -> bool {
+        self.record.is_better_than(other, ranking)
+    }
+
+    pub fn lurd(&self) -> &str {
+        self.record.lurd()
     }
 
     pub fn moves_string(&self) -> String {
         self.record.moves_string()
     }
 
+    pub fn pushes_string(&self) -> String {
+        self.record.pushes_string()
+    }
+
     pub fn time_string(&self) -> String {
         self.record.time_string()
     }
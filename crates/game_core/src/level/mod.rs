@@ -1,14 +1,37 @@
+mod code;
+mod code_error;
 mod data;
+mod deadlock;
+mod difficulty;
+mod history;
 mod kind;
 mod level;
+mod mode;
+mod pack;
+mod path;
+mod problem;
+mod ranking;
 mod record;
-mod snapshots;
 mod state;
+mod undo_policy;
 mod update;
+mod xsb;
+mod xsb_error;
 
+pub(crate) use deadlock::frozen_squares;
+
+pub use code_error::LevelCodeError;
+pub use deadlock::Deadlock;
+pub use difficulty::{Difficulty, DifficultyLabel};
+pub use history::LevelHistory;
 pub use kind::LevelKind;
 pub use level::Level;
+pub use mode::PlayMode;
+pub use pack::{LevelPack, PackLevel};
+pub use problem::LevelProblem;
+pub use ranking::RecordRanking;
 pub use record::LevelRecord;
-pub use snapshots::LevelSnapshots;
-pub use state::LevelState;
+pub use state::{COLORS, LevelState};
+pub use undo_policy::UndoPolicy;
 pub use update::LevelUpdate;
+pub use xsb_error::XsbError;
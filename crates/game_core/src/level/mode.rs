@@ -0,0 +1,34 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Rules the character plays by, pulling starts from the solved level and goes back
+/// to where the boxes were at the beginning
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum PlayMode {
+    #[default]
+    Push,
+    Pull,
+}
+
+impl PlayMode {
+    pub fn is_pull(&self) -> bool {
+        matches!(self, PlayMode::Pull)
+    }
+
+    pub fn toggle(&self) -> PlayMode {
+        match self {
+            PlayMode::Push => PlayMode::Pull,
+            PlayMode::Pull => PlayMode::Push,
+        }
+    }
+}
+
+impl fmt::Display for PlayMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayMode::Push => write!(f, "push"),
+            PlayMode::Pull => write!(f, "pull"),
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::versioned::Migrate;
+
+use super::{state::LevelState, undo_policy::UndoPolicy};
+
+/// Ordered set of levels, with the credits of whoever made them
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct LevelPack {
+    title: String,
+    #[serde(default)]
+    author: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    license: String,
+    /// Undo budget of every level, instead of the one in the options
+    #[serde(default)]
+    undo_policy: Option<UndoPolicy>,
+    levels: Vec<PackLevel>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PackLevel {
+    name: String,
+    state: LevelState,
+    #[serde(default)]
+    par_moves: Option<usize>,
+    #[serde(default)]
+    par_pushes: Option<usize>,
+    #[serde(default)]
+    hint: Option<String>,
+    /// Undo budget of the level, instead of the one of the pack
+    #[serde(default)]
+    undo_policy: Option<UndoPolicy>,
+}
+
+impl LevelPack {
+    pub fn new(title: &str) -> LevelPack {
+        LevelPack {
+            title: title.to_string(),
+            ..LevelPack::default()
+        }
+    }
+
+    /// Pack of a single level, the title is the name of the level
+    pub fn single(name: &str, state: LevelState) -> LevelPack {
+        LevelPack::new(name).with_level(PackLevel::new(name, state))
+    }
+
+    pub fn with_author(mut self, author: &str) -> Self {
+        self.author = author.to_string();
+        self
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    pub fn with_license(mut self, license: &str) -> Self {
+        self.license = license.to_string();
+        self
+    }
+
+    pub fn with_undo_policy(mut self, undo_policy: UndoPolicy) -> Self {
+        self.undo_policy = Some(undo_policy);
+        self
+    }
+
+    pub fn with_level(mut self, level: PackLevel) -> Self {
+        self.levels.push(level);
+        self
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn license(&self) -> &str {
+        &self.license
+    }
+
+    pub fn undo_policy(&self) -> Option<UndoPolicy> {
+        self.undo_policy
+    }
+
+    /// Undo budget set by the level or else by the pack, if any
+    pub fn level_undo_policy(&self, index: usize) -> Option<UndoPolicy> {
+        self.level(index)
+            .and_then(PackLevel::undo_policy)
+            .or(self.undo_policy)
+    }
+
+    pub fn levels(&self) -> &[PackLevel] {
+        &self.levels
+    }
+
+    pub fn level(&self, index: usize) -> Option<&PackLevel> {
+        self.levels.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+}
+
+impl PackLevel {
+    pub fn new(name: &str, state: LevelState) -> PackLevel {
+        PackLevel {
+            name: name.to_string(),
+            state,
+            par_moves: None,
+            par_pushes: None,
+            hint: None,
+            undo_policy: None,
+        }
+    }
+
+    pub fn with_par_moves(mut self, moves: usize) -> Self {
+        self.par_moves = Some(moves);
+        self
+    }
+
+    pub fn with_par_pushes(mut self, pushes: usize) -> Self {
+        self.par_pushes = Some(pushes);
+        self
+    }
+
+    pub fn with_hint(mut self, hint: &str) -> Self {
+        self.hint = Some(hint.to_string());
+        self
+    }
+
+    pub fn with_undo_policy(mut self, undo_policy: UndoPolicy) -> Self {
+        self.undo_policy = Some(undo_policy);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn state(&self) -> &LevelState {
+        &self.state
+    }
+
+    pub fn par_moves(&self) -> Option<usize> {
+        self.par_moves
+    }
+
+    pub fn par_pushes(&self) -> Option<usize> {
+        self.par_pushes
+    }
+
+    pub fn hint(&self) -> Option<&str> {
+        self.hint.as_deref()
+    }
+
+    pub fn undo_policy(&self) -> Option<UndoPolicy> {
+        self.undo_policy
+    }
+
+    /// Text with the par moves and pushes that are set, if any
+    pub fn par_string(&self) -> Option<String> {
+        match (self.par_moves, self.par_pushes) {
+            (Some(moves), Some(pushes)) => Some(format!("Par: {moves} moves, {pushes} pushes")),
+            (Some(moves), None) => Some(format!("Par: {moves} moves")),
+            (None, Some(pushes)) => Some(format!("Par: {pushes} pushes")),
+            (None, None) => None,
+        }
+    }
+}
+
+impl Migrate for LevelPack {
+    const VERSION: u32 = 2;
+
+    /// Level files held a single level up to the version 1, and only lack the fields
+    /// that have defaults
+    fn migrate<'de, D: Deserializer<'de>>(
+        version: u32,
+        deserializer: D,
+    ) -> Result<LevelPack, D::Error> {
+        match version {
+            0 | 1 => {
+                LevelState::deserialize(deserializer).map(|state| LevelPack::single("", state))
+            }
+            2 => LevelPack::deserialize(deserializer),
+            _ => unreachable!("Newer versions are rejected before the migration"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{map::MapPosition, versioned::VersionError};
+
+    /// Level file as it was written before there were packs and versions
+    const LEGACY_LEVEL: &str = "(
+        map: ((
+            (V,V,V,V,V,V,V,V,V,V,),
+            (V,V,V,V,V,V,V,V,V,V,),
+            (V,V,Z,V,F,F,F,F,V,V,),
+            (V,V,Z,V,F,B,B,F,V,V,),
+            (V,V,Z,V,F,F,B,F,V,V,),
+            (V,V,Z,V,V,F,F,F,V,V,),
+            (V,V,F,F,B,F,F,V,V,V,),
+            (V,V,F,F,V,F,F,V,V,V,),
+            (V,V,V,V,V,V,V,V,V,V,),
+            (V,V,V,V,V,V,V,V,V,V,),
+        )),
+        character_position: (x: 2, y: 7),
+        character_facing_direction: 1,
+        remaining_zones: 4,
+    )";
+
+    #[derive(Serialize, Deserialize)]
+    struct LevelFile(#[serde(with = "crate::versioned")] LevelPack);
+
+    fn read(text: &str) -> Result<LevelPack, ron::error::SpannedError> {
+        ron::from_str::<LevelFile>(text).map(|LevelFile(pack)| pack)
+    }
+
+    fn assert_legacy_level(pack: &LevelPack) {
+        assert_eq!(pack.len(), 1);
+        let state = pack.level(0).unwrap().state();
+        assert_eq!((state.map().width(), state.map().height()), (10, 10));
+        assert_eq!(state.character_position(), MapPosition::new(2, 7));
+        assert_eq!(state.character_facing_direction(), 1);
+        assert_eq!(state.remaining_zones(), 4);
+    }
+
+    #[test]
+    fn migrate_bare_legacy_level() {
+        assert_legacy_level(&read(&format!("({LEGACY_LEVEL})")).unwrap());
+    }
+
+    #[test]
+    fn migrate_version_1() {
+        let text = format!("((version: 1, data: {LEGACY_LEVEL}))");
+        assert_legacy_level(&read(&text).unwrap());
+    }
+
+    #[test]
+    fn round_trip() {
+        let state = read(&format!("({LEGACY_LEVEL})"))
+            .unwrap()
+            .level(0)
+            .unwrap()
+            .state()
+            .clone();
+        let pack = LevelPack::new("Pack")
+            .with_author("Someone")
+            .with_level(PackLevel::new("Level 1", state).with_par_pushes(9));
+        let text = ron::to_string(&LevelFile(pack)).unwrap();
+        assert!(text.starts_with("((version:2,data:"));
+
+        let pack = read(&text).unwrap();
+        assert_eq!((pack.title(), pack.author()), ("Pack", "Someone"));
+        assert_eq!(pack.level(0).unwrap().name(), "Level 1");
+        assert_eq!(pack.level(0).unwrap().par_pushes(), Some(9));
+        assert_legacy_level(&pack);
+    }
+
+    #[test]
+    fn reject_newer_version() {
+        let text = format!("((version: 3, data: {LEGACY_LEVEL}))");
+        let newer = VersionError::Newer {
+            version: 3,
+            supported: LevelPack::VERSION,
+        };
+        assert_eq!(
+            read(&text).err().map(|error| error.code),
+            Some(ron::Error::Message(newer.to_string()))
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::VecDeque;
+
+use crate::{input::Direction, map::MapPosition};
+
+use super::state::LevelState;
+
+/// Shortest walk of the character to the target that never moves a box, when pulling
+/// the steps away from a box are left out since the box would follow the character
+pub(super) fn find_path(
+    state: &LevelState,
+    target: &MapPosition,
+    pulling: bool,
+) -> Option<Vec<Direction>> {
+    let map = state.map();
+    if !map.contains(target) {
+        return None;
+    }
+
+    let board = state.board();
+    let boxes = board.boxes(map);
+    let mut obstacles: Vec<usize> = boxes.iter().copied().chain(board.holes()).collect();
+    obstacles.sort_unstable();
+
+    let start = board.index(&state.character_position());
+    let target = board.index(target);
+    let mut previous: Vec<Option<(usize, Direction)>> = vec![None; board.cells()];
+    let mut reached = vec![false; board.cells()];
+    let mut queue = VecDeque::from([start]);
+    reached[start] = true;
+
+    while let Some(index) = queue.pop_front() {
+        if index == target {
+            break;
+        }
+
+        for direction in Direction::ALL {
+            if pulling
+                && board
+                    .open_step(index, &direction.opposite())
+                    .is_some_and(|behind| boxes.binary_search(&behind).is_ok())
+            {
+                continue;
+            }
+            let Some(next) = board.open_step(index, &direction) else {
+                continue;
+            };
+            if obstacles.binary_search(&next).is_ok() || !board.can_enter(next, index, &boxes) {
+                continue;
+            }
+
+            let next = board.land(next, &direction, &obstacles);
+            if !reached[next] {
+                reached[next] = true;
+                previous[next] = Some((index, direction));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    if !reached[target] {
+        return None;
+    }
+
+    let mut directions = vec![];
+    let mut current = target;
+    while let Some((index, direction)) = previous[current] {
+        directions.push(direction);
+        current = index;
+    }
+    directions.reverse();
+    Some(directions)
+}
@@ -0,0 +1,182 @@
+use std::fmt;
+
+use crate::{
+    input::Direction,
+    map::{MapEntity, MapPosition},
+};
+
+use super::state::{COLORS, LevelState};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LevelProblem {
+    NoZones,
+    /// Extra boxes are fine as long as the holes and the plates can take them
+    BoxesZonesMismatch {
+        boxes: usize,
+        zones: usize,
+    },
+    ColorMismatch {
+        color: u8,
+        boxes: usize,
+        zones: usize,
+    },
+    /// Zones without a box of their color count as empty
+    RemainingZonesMismatch {
+        remaining: usize,
+        empty: usize,
+    },
+    CharacterOutsideMap,
+    CharacterOnVoid,
+    CharacterOnBox,
+    /// Teleporter without a partner
+    UnlinkedTeleporter(MapPosition),
+    /// Door without plates, which never opens
+    UnlinkedDoor(MapPosition),
+    /// Boxes walled off from the character
+    UnreachableBoxes(Vec<MapPosition>),
+}
+
+impl fmt::Display for LevelProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelProblem::NoZones => write!(f, "There are no zones"),
+            LevelProblem::BoxesZonesMismatch { boxes, zones } => {
+                write!(f, "There are {boxes} boxes for {zones} zones")
+            }
+            LevelProblem::ColorMismatch {
+                color,
+                boxes,
+                zones,
+            } => write!(
+                f,
+                "There are {boxes} boxes for {zones} zones of color {color}"
+            ),
+            LevelProblem::RemainingZonesMismatch { remaining, empty } => {
+                write!(
+                    f,
+                    "{remaining} zones are left to fill, but {empty} are not filled"
+                )
+            }
+            LevelProblem::CharacterOutsideMap => write!(f, "The character is outside of the map"),
+            LevelProblem::CharacterOnVoid => write!(f, "The character stands on the void"),
+            LevelProblem::CharacterOnBox => write!(f, "The character stands on a box"),
+            LevelProblem::UnlinkedTeleporter(position) => write!(
+                f,
+                "The teleporter at {}, {} is not linked",
+                position.x(),
+                position.y()
+            ),
+            LevelProblem::UnlinkedDoor(position) => write!(
+                f,
+                "The door at {}, {} has no plates",
+                position.x(),
+                position.y()
+            ),
+            LevelProblem::UnreachableBoxes(positions) => {
+                write!(f, "{} boxes cannot be reached", positions.len())
+            }
+        }
+    }
+}
+
+pub(super) fn find_problems(state: &LevelState) -> Vec<LevelProblem> {
+    let map = state.map();
+    let count =
+        |f: fn(&MapEntity) -> bool| map.positions().filter(|position| f(&map[position])).count();
+    let boxes = count(|entity| entity.has_box());
+    let zones = count(|entity| matches!(entity, MapEntity::Z | MapEntity::P));
+    let empty = state.unmatched_zones();
+    // every zone needs a box, and the boxes left over can only go into the holes or
+    // rest on the plates
+    let spare = count(|entity| matches!(entity, MapEntity::H | MapEntity::S | MapEntity::Q));
+    let enough = |boxes: usize, zones: usize| boxes >= zones && boxes <= zones + spare;
+    let mut problems = vec![];
+
+    if zones == 0 {
+        problems.push(LevelProblem::NoZones);
+    }
+    if !enough(boxes, zones) {
+        problems.push(LevelProblem::BoxesZonesMismatch { boxes, zones });
+    } else {
+        for color in 0..COLORS {
+            let boxes = map
+                .positions()
+                .filter(|position| map[position].has_box() && state.box_color(position) == color)
+                .count();
+            let zones = map
+                .positions()
+                .filter(|position| {
+                    matches!(map[position], MapEntity::Z | MapEntity::P)
+                        && state.zone_color(position) == color
+                })
+                .count();
+            if !enough(boxes, zones) {
+                problems.push(LevelProblem::ColorMismatch {
+                    color,
+                    boxes,
+                    zones,
+                });
+            }
+        }
+    }
+    if state.remaining_zones() != empty {
+        problems.push(LevelProblem::RemainingZonesMismatch {
+            remaining: state.remaining_zones(),
+            empty,
+        });
+    }
+
+    let character = state.character_position();
+    if !map.contains(&character) {
+        problems.push(LevelProblem::CharacterOutsideMap);
+        return problems;
+    }
+    match map[&character] {
+        MapEntity::V => problems.push(LevelProblem::CharacterOnVoid),
+        entity if entity.has_box() => problems.push(LevelProblem::CharacterOnBox),
+        _ => (),
+    }
+
+    for position in map.positions() {
+        if map[&position].is_teleporter() && state.teleporter_partner(&position).is_none() {
+            problems.push(LevelProblem::UnlinkedTeleporter(position));
+        }
+        if matches!(map[&position], MapEntity::D) && state.door_plates(&position).next().is_none() {
+            problems.push(LevelProblem::UnlinkedDoor(position));
+        }
+    }
+
+    // boxes can be pushed out of the way, so only walls split the map
+    let board = state.board();
+    let mut reached = vec![false; board.cells()];
+    let mut pending = vec![board.index(&character)];
+    reached[board.index(&character)] = true;
+    while let Some(index) = pending.pop() {
+        for direction in Direction::ALL {
+            if let Some(next_index) = board.open_step(index, &direction)
+                && !reached[next_index]
+            {
+                reached[next_index] = true;
+                pending.push(next_index);
+            }
+        }
+        if let Some(partner) = board.teleporter(index)
+            && !reached[partner]
+        {
+            reached[partner] = true;
+            pending.push(partner);
+        }
+    }
+
+    let unreachable: Vec<MapPosition> = board
+        .boxes(map)
+        .into_iter()
+        .filter(|index| !reached[*index])
+        .map(|index| board.position(index))
+        .collect();
+    if !unreachable.is_empty() {
+        problems.push(LevelProblem::UnreachableBoxes(unreachable));
+    }
+
+    problems
+}
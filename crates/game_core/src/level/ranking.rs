@@ -0,0 +1,29 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// What a record has to beat first, the time only breaks the ties
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum RecordRanking {
+    #[default]
+    Moves,
+    Pushes,
+}
+
+impl RecordRanking {
+    pub fn toggle(&self) -> RecordRanking {
+        match self {
+            RecordRanking::Moves => RecordRanking::Pushes,
+            RecordRanking::Pushes => RecordRanking::Moves,
+        }
+    }
+}
+
+impl fmt::Display for RecordRanking {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordRanking::Moves => write!(f, "Moves"),
+            RecordRanking::Pushes => write!(f, "Pushes"),
+        }
+    }
+}
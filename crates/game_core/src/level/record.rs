@@ -2,10 +2,19 @@ use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
+use super::{ranking::RecordRanking, undo_policy::UndoPolicy};
+
 #[derive(Default, Serialize, Deserialize, Clone)]
 pub struct LevelRecord {
     moves: usize,
+    /// Moves that took a box along, records saved before they were counted have none
+    #[serde(default)]
+    pushes: usize,
     time: f32,
+    #[serde(default)]
+    undo_policy: UndoPolicy,
+    #[serde(default)]
+    lurd: String,
 }
 
 impl LevelRecord {
@@ -13,16 +22,42 @@ impl LevelRecord {
         self.moves > 0
     }
 
+    pub fn undo_policy(&self) -> UndoPolicy {
+        self.undo_policy
+    }
+
+    pub fn set_undo_policy(&mut self, undo_policy: UndoPolicy) {
+        self.undo_policy = undo_policy;
+    }
+
+    pub fn lurd(&self) -> &str {
+        &self.lurd
+    }
+
     pub fn reset_moves(&mut self) {
         self.moves = 0;
+        self.pushes = 0;
+        self.lurd.clear();
     }
 
-    pub fn increment_moves(&mut self) {
+    /// Uppercase steps move a box
+    pub fn push_move(&mut self, step: char) {
         self.moves = self.moves.saturating_add(1);
+        if step.is_ascii_uppercase() {
+            self.pushes = self.pushes.saturating_add(1);
+        }
+        self.lurd.push(step);
     }
 
-    pub fn decrement_moves(&mut self) {
+    pub fn pop_move(&mut self) {
         self.moves = self.moves.saturating_sub(1);
+        if self
+            .lurd
+            .pop()
+            .is_some_and(|step| step.is_ascii_uppercase())
+        {
+            self.pushes = self.pushes.saturating_sub(1);
+        }
     }
 
     pub fn time_string(&self) -> String {
@@ -37,6 +72,10 @@ impl LevelRecord {
         self.moves.to_string()
     }
 
+    pub fn pushes_string(&self) -> String {
+        self.pushes.to_string()
+    }
+
     pub fn tick(&mut self, delta: Duration) {
         self.time += delta.as_secs_f32();
     }
@@ -44,12 +83,33 @@ impl LevelRecord {
     pub fn moves_in_time(&self, separator: char) -> String {
         let moves = self.moves_string();
         let time = self.time_string();
-        format!("{moves} moves{separator}in {time}")
+        let marker = if self.undo_policy.is_unlimited() {
+            "*"
+        } else {
+            ""
+        };
+        format!(
+            "{moves} moves, {} pushes{marker}{separator}in {time}",
+            self.pushes
+        )
     }
 
-    pub fn is_better_than(&self, other: &LevelRecord) -> bool {
-        !other.is_set()
-            || self.moves < other.moves
-            || self.moves <= other.moves && self.time < other.time
+    pub fn is_better_than(&self, other: &LevelRecord, ranking: RecordRanking) -> bool {
+        if !other.is_set() {
+            return true;
+        }
+
+        // every level needs a push, so a record without them was saved before they were counted
+        let ranking = if other.pushes == 0 {
+            RecordRanking::Moves
+        } else {
+            ranking
+        };
+        let score = |record: &LevelRecord| match ranking {
+            RecordRanking::Moves => (record.moves, record.pushes),
+            RecordRanking::Pushes => (record.pushes, record.moves),
+        };
+        let (score, other_score) = (score(self), score(other));
+        score < other_score || score == other_score && self.time < other.time
     }
 }
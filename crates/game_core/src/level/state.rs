@@ -1,16 +1,91 @@
+use std::collections::VecDeque;
+
 use serde::{Deserialize, Serialize};
 
-use crate::map::{Map, MapEntity, MapPosition};
+use crate::{
+    input::Direction,
+    map::{Board, Map, MapEntity, MapPosition},
+    solver::{Solver, SolverError, find_push_path},
+};
+
+use super::{
+    code,
+    code_error::LevelCodeError,
+    deadlock::{Deadlock, find_deadlocks},
+    difficulty::Difficulty,
+    path::find_path,
+    problem::{LevelProblem, find_problems},
+    xsb,
+    xsb_error::XsbError,
+};
+
+/// Boxes and zones have the color 0 unless they are given another one below this
+pub const COLORS: u8 = 4;
 
-#[derive(Serialize, Deserialize, Default, Clone, Copy)]
+#[derive(Serialize, Deserialize, Default, Clone)]
 pub struct LevelState {
     map: Map,
     character_position: MapPosition,
     character_facing_direction: usize,
     remaining_zones: usize,
+    /// Pairs of linked teleporters
+    #[serde(default)]
+    teleporters: Vec<(MapPosition, MapPosition)>,
+    /// Pressure plates and the doors they open
+    #[serde(default)]
+    switches: Vec<(MapPosition, MapPosition)>,
+    /// Boxes and zones with a color other than 0, a box only counts as placed
+    /// on a zone of its own color
+    #[serde(default)]
+    box_colors: Vec<(MapPosition, u8)>,
+    #[serde(default)]
+    zone_colors: Vec<(MapPosition, u8)>,
 }
 
 impl LevelState {
+    pub fn new(map: Map, character_position: MapPosition) -> LevelState {
+        let remaining_zones = map
+            .positions()
+            .filter(|position| matches!(map[position], MapEntity::Z))
+            .count();
+
+        LevelState {
+            map,
+            character_position,
+            character_facing_direction: 0,
+            remaining_zones,
+            teleporters: vec![],
+            switches: vec![],
+            box_colors: vec![],
+            zone_colors: vec![],
+        }
+    }
+
+    pub fn from_xsb(text: &str) -> Result<LevelState, XsbError> {
+        xsb::parse(text)
+    }
+
+    pub fn from_xsb_collection(text: &str) -> Vec<Result<LevelState, XsbError>> {
+        xsb::parse_collection(text)
+    }
+
+    pub fn to_xsb(&self) -> String {
+        xsb::write(self)
+    }
+
+    /// Short text to share the level, see `from_code`
+    pub fn to_code(&self) -> String {
+        code::encode(self)
+    }
+
+    pub fn from_code(code: &str) -> Result<LevelState, LevelCodeError> {
+        code::decode(code)
+    }
+
+    pub fn map(&self) -> &Map {
+        &self.map
+    }
+
     pub fn get_entity(&self, position: &MapPosition) -> &MapEntity {
         &self.map[position]
     }
@@ -35,6 +110,10 @@ impl LevelState {
         self.character_facing_direction = direction;
     }
 
+    pub fn remaining_zones(&self) -> usize {
+        self.remaining_zones
+    }
+
     pub fn increment_remaining_zones(&mut self) {
         self.remaining_zones += 1;
     }
@@ -46,4 +125,304 @@ impl LevelState {
     pub fn no_remaining_zones(&self) -> bool {
         self.remaining_zones == 0
     }
+
+    pub fn box_color(&self, position: &MapPosition) -> u8 {
+        color_at(&self.box_colors, position)
+    }
+
+    pub fn zone_color(&self, position: &MapPosition) -> u8 {
+        color_at(&self.zone_colors, position)
+    }
+
+    /// The remaining zones are kept up to date, as long as the entity is not changed later
+    pub fn set_box_color(&mut self, position: MapPosition, color: u8) {
+        let placed = self.is_placed(&position);
+        set_color(&mut self.box_colors, position, color);
+        self.update_placed(&position, placed);
+    }
+
+    pub fn set_zone_color(&mut self, position: MapPosition, color: u8) {
+        let placed = self.is_placed(&position);
+        set_color(&mut self.zone_colors, position, color);
+        self.update_placed(&position, placed);
+    }
+
+    /// Carries the color of a box that is being moved, the entities are not changed
+    pub fn move_box_color(&mut self, from: &MapPosition, to: MapPosition) {
+        let color = self.box_color(from);
+        set_color(&mut self.box_colors, *from, 0);
+        set_color(&mut self.box_colors, to, color);
+    }
+
+    /// Whether there is a box on a zone of its color
+    pub fn is_placed(&self, position: &MapPosition) -> bool {
+        matches!(self.map[position], MapEntity::P)
+            && self.box_color(position) == self.zone_color(position)
+    }
+
+    /// Zones without a box of their color, which should match the remaining zones
+    pub fn unmatched_zones(&self) -> usize {
+        self.map
+            .positions()
+            .filter(|position| {
+                matches!(self.map[position], MapEntity::Z | MapEntity::P)
+                    && !self.is_placed(position)
+            })
+            .count()
+    }
+
+    fn update_placed(&mut self, position: &MapPosition, placed: bool) {
+        match (placed, self.is_placed(position)) {
+            (true, false) => self.increment_remaining_zones(),
+            (false, true) => self.decrement_remaining_zones(),
+            _ => (),
+        }
+    }
+
+    pub fn teleporters(&self) -> &[(MapPosition, MapPosition)] {
+        &self.teleporters
+    }
+
+    /// Only links between two teleporters count, the map may have changed under the others
+    pub fn teleporter_partner(&self, position: &MapPosition) -> Option<MapPosition> {
+        self.linked_teleporters().find_map(|(pad, partner)| {
+            if pad == position {
+                Some(*partner)
+            } else if partner == position {
+                Some(*pad)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Any previous link of both teleporters is replaced
+    pub fn link_teleporters(&mut self, pad: MapPosition, partner: MapPosition) {
+        self.unlink_teleporter(&pad);
+        self.unlink_teleporter(&partner);
+        self.teleporters.push((pad, partner));
+    }
+
+    pub fn unlink_teleporter(&mut self, position: &MapPosition) {
+        self.teleporters
+            .retain(|(pad, partner)| pad != position && partner != position);
+    }
+
+    fn linked_teleporters(&self) -> impl Iterator<Item = &(MapPosition, MapPosition)> {
+        let is_pad = |position: &MapPosition| {
+            self.map.contains(position) && self.map[position].is_teleporter()
+        };
+        self.teleporters
+            .iter()
+            .filter(move |(pad, partner)| is_pad(pad) && is_pad(partner))
+    }
+
+    pub fn switches(&self) -> &[(MapPosition, MapPosition)] {
+        &self.switches
+    }
+
+    pub fn link_switch(&mut self, plate: MapPosition, door: MapPosition) {
+        if !self.switches.contains(&(plate, door)) {
+            self.switches.push((plate, door));
+        }
+    }
+
+    /// Removes the links of the plate or the door in the given position
+    pub fn unlink_switch(&mut self, position: &MapPosition) {
+        self.switches
+            .retain(|(plate, door)| plate != position && door != position);
+    }
+
+    /// Links and colors left outside of the map are dropped, and the character is
+    /// moved back into it
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.map.resize(width, height);
+
+        let map = &self.map;
+        self.teleporters
+            .retain(|(pad, partner)| map.contains(pad) && map.contains(partner));
+        self.switches
+            .retain(|(plate, door)| map.contains(plate) && map.contains(door));
+        self.box_colors
+            .retain(|(position, _)| map.contains(position));
+        self.zone_colors
+            .retain(|(position, _)| map.contains(position));
+
+        self.character_position = MapPosition::new(
+            self.character_position.x().min(width - 1),
+            self.character_position.y().min(height - 1),
+        );
+        self.remaining_zones = self.unmatched_zones();
+    }
+
+    /// Plates of the door in the given position, the map may have changed under the other links
+    pub fn door_plates(&self, position: &MapPosition) -> impl Iterator<Item = MapPosition> + '_ {
+        let position = *position;
+        self.linked_switches()
+            .filter(move |(_, door)| *door == position)
+            .map(|(plate, _)| *plate)
+    }
+
+    pub fn is_pressed(&self, plate: &MapPosition) -> bool {
+        *plate == self.character_position || matches!(self.map[plate], MapEntity::Q)
+    }
+
+    /// A door stays open while the character is in it
+    pub fn is_door_open(&self, position: &MapPosition) -> bool {
+        matches!(self.map[position], MapEntity::D)
+            && (*position == self.character_position
+                || self
+                    .door_plates(position)
+                    .any(|plate| self.is_pressed(&plate)))
+    }
+
+    pub fn open_doors(&self) -> Vec<MapPosition> {
+        self.map
+            .positions()
+            .filter(|position| self.is_door_open(position))
+            .collect()
+    }
+
+    fn linked_switches(&self) -> impl Iterator<Item = &(MapPosition, MapPosition)> {
+        self.switches.iter().filter(|(plate, door)| {
+            self.map.contains(plate)
+                && self.map.contains(door)
+                && self.map[plate].is_plate()
+                && matches!(self.map[door], MapEntity::D)
+        })
+    }
+
+    /// The solved level to play in pull mode, with the boxes on the zones and the zones
+    /// where the boxes were. Only the classic tiles can be played backwards, and every box
+    /// needs a zone
+    pub fn reversed(&self) -> Option<LevelState> {
+        let board = self.board();
+        let boxes = board.boxes(&self.map);
+        if !board.is_classic() || boxes.len() != board.zones().count() {
+            return None;
+        }
+
+        let mut map = self.map.clone();
+        for position in self.map.positions() {
+            map[&position] = match (
+                self.map[&position].has_box(),
+                board.is_zone(board.index(&position)),
+            ) {
+                (true, true) => MapEntity::P,
+                (true, false) => MapEntity::Z,
+                (false, true) => MapEntity::B,
+                (false, false) => self.map[&position],
+            };
+        }
+
+        // the forward game ends right after a push, so the character starts on the closest
+        // cell where it can pull a box, or at least on one that is free
+        let has_box = |index: usize| map[&board.position(index)].has_box();
+        let can_pull = |index: usize| {
+            Direction::ALL.iter().any(|direction| {
+                board
+                    .open_step(index, &direction.opposite())
+                    .is_some_and(has_box)
+                    && board
+                        .open_step(index, direction)
+                        .is_some_and(|next| !has_box(next))
+            })
+        };
+        let start = board.index(&self.character_position);
+        let mut free = None;
+        let mut character = None;
+        let mut reached = vec![false; board.cells()];
+        let mut pending = VecDeque::from([start]);
+        reached[start] = true;
+        while let Some(index) = pending.pop_front() {
+            if !has_box(index) {
+                free = free.or(Some(index));
+                if can_pull(index) {
+                    character = Some(index);
+                    break;
+                }
+            }
+            for direction in Direction::ALL {
+                if let Some(next) = board.open_step(index, &direction)
+                    && !reached[next]
+                {
+                    reached[next] = true;
+                    pending.push_back(next);
+                }
+            }
+        }
+        let character = character.or(free).unwrap_or(start);
+
+        let mut state = LevelState {
+            map,
+            character_position: board.position(character),
+            character_facing_direction: self.character_facing_direction,
+            remaining_zones: 0,
+            teleporters: vec![],
+            switches: vec![],
+            box_colors: self.zone_colors.clone(),
+            zone_colors: self.box_colors.clone(),
+        };
+        state.remaining_zones = state.unmatched_zones();
+        Some(state)
+    }
+
+    pub(crate) fn board(&self) -> Board {
+        let teleporters: Vec<_> = self.linked_teleporters().copied().collect();
+        let switches: Vec<_> = self.linked_switches().copied().collect();
+        Board::new(&self.map)
+            .with_teleporters(&teleporters)
+            .with_switches(&switches)
+    }
+
+    /// Directions that walk the character to the target without pushing any box
+    pub fn path_to(&self, target: &MapPosition) -> Option<Vec<Direction>> {
+        find_path(self, target, false)
+    }
+
+    /// Directions that take the box to the target pushing no other box
+    pub fn push_path(
+        &self,
+        box_position: &MapPosition,
+        target: &MapPosition,
+    ) -> Option<Vec<Direction>> {
+        find_push_path(self, box_position, target)
+    }
+
+    pub fn deadlocks(&self) -> Vec<Deadlock> {
+        let board = self.board();
+        find_deadlocks(&board, &board.boxes(&self.map))
+    }
+
+    pub fn is_deadlocked(&self) -> bool {
+        !self.deadlocks().is_empty()
+    }
+
+    pub fn difficulty(&self, solver: &Solver) -> Result<Difficulty, SolverError> {
+        solver
+            .solve(self)
+            .map(|solution| Difficulty::new(self, &solution))
+    }
+
+    pub fn validate(&self) -> Vec<LevelProblem> {
+        find_problems(self)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_empty()
+    }
+}
+
+fn color_at(colors: &[(MapPosition, u8)], position: &MapPosition) -> u8 {
+    colors
+        .iter()
+        .find(|(colored, _)| colored == position)
+        .map_or(0, |(_, color)| *color)
+}
+
+fn set_color(colors: &mut Vec<(MapPosition, u8)>, position: MapPosition, color: u8) {
+    colors.retain(|(colored, _)| *colored != position);
+    if color != 0 {
+        colors.push((position, color));
+    }
 }
@@ -0,0 +1,32 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_UNDOS: usize = 4;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UndoPolicy {
+    Limited(usize),
+    Unlimited,
+}
+
+impl Default for UndoPolicy {
+    fn default() -> Self {
+        UndoPolicy::Limited(DEFAULT_UNDOS)
+    }
+}
+
+impl UndoPolicy {
+    pub fn is_unlimited(&self) -> bool {
+        matches!(self, UndoPolicy::Unlimited)
+    }
+}
+
+impl fmt::Display for UndoPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UndoPolicy::Limited(undos) => write!(f, "{undos}"),
+            UndoPolicy::Unlimited => write!(f, "No limit"),
+        }
+    }
+}
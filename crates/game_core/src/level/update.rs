@@ -1,8 +1,17 @@
 pub enum LevelUpdate {
     PushBox,
     PlaceBox,
+    PullBox,
+    SlideBox,
+    TeleportBox,
+    FillHole,
     MoveCharacter,
+    SlideCharacter,
+    TeleportCharacter,
+    DoorOpened,
+    DoorClosed,
     UndoMove,
+    RedoMove,
     Reload,
     Exit,
 }
@@ -0,0 +1,278 @@
+use crate::map::{MAP_COLS, MAP_ROWS, MAX_MAP_COLS, MAX_MAP_ROWS, Map, MapEntity, MapPosition};
+
+use super::{state::LevelState, xsb_error::XsbError};
+
+/// Ice, teleporters and holes have no standard tiles, `~`, `=`, the digits and `^` are
+/// only understood by this game, each pair of teleporters shares a digit
+const XSB_TILES: [char; 21] = [
+    '#', ' ', '-', '_', '.', '$', '*', '@', '+', '~', '=', '1', '2', '3', '4', '5', '6', '7', '8',
+    '9', '^',
+];
+const TELEPORTER_TILES: usize = 9;
+
+/// Walls and the floor outside of the walls become void, and levels smaller
+/// than a new map are centered in one
+pub(super) fn parse(text: &str) -> Result<LevelState, XsbError> {
+    let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    let first = lines.iter().position(|line| !line.is_empty());
+    let last = lines.iter().rposition(|line| !line.is_empty());
+    let (Some(first), Some(last)) = (first, last) else {
+        return Err(XsbError::Empty);
+    };
+    let lines = &lines[first..=last];
+
+    let cols = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or_default();
+    let rows = lines.len();
+    let mut tiles: Vec<Vec<Option<MapEntity>>> = vec![vec![None; cols]; rows];
+    let mut player = None;
+    let mut pads: Vec<Vec<(usize, usize)>> = vec![vec![]; TELEPORTER_TILES];
+
+    for (y, line) in lines.iter().enumerate() {
+        for (x, tile) in line.chars().enumerate() {
+            tiles[y][x] = match tile {
+                '#' => None,
+                ' ' | '-' | '_' => Some(MapEntity::F),
+                '.' => Some(MapEntity::Z),
+                '$' => Some(MapEntity::B),
+                '*' => Some(MapEntity::P),
+                '~' => Some(MapEntity::I),
+                '=' => Some(MapEntity::J),
+                '^' => Some(MapEntity::H),
+                '1'..='9' => {
+                    pads[tile as usize - '1' as usize].push((x, y));
+                    Some(MapEntity::T)
+                }
+                '@' | '+' => {
+                    if player.replace((x, y)).is_some() {
+                        return Err(XsbError::TooManyPlayers);
+                    }
+                    Some(if tile == '@' {
+                        MapEntity::F
+                    } else {
+                        MapEntity::Z
+                    })
+                }
+                _ => return Err(XsbError::UnknownTile(tile)),
+            };
+        }
+    }
+
+    let Some(player) = player else {
+        return Err(XsbError::NoPlayer);
+    };
+
+    let mut links = vec![];
+    for (digit, positions) in pads.iter().enumerate() {
+        match positions[..] {
+            [] => (),
+            [pad, partner] => links.push((pad, partner)),
+            _ => {
+                return Err(XsbError::UnpairedTeleporter(char::from(b'1' + digit as u8)));
+            }
+        }
+    }
+    let partner = |tile: (usize, usize)| {
+        links.iter().find_map(|(pad, partner)| {
+            if *pad == tile {
+                Some(*partner)
+            } else if *partner == tile {
+                Some(*pad)
+            } else {
+                None
+            }
+        })
+    };
+
+    let mut reached = vec![vec![false; cols]; rows];
+    let mut pending = vec![player];
+    reached[player.1][player.0] = true;
+    while let Some((x, y)) = pending.pop() {
+        let neighbours = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        for (next_x, next_y) in neighbours {
+            if next_x < cols
+                && next_y < rows
+                && tiles[next_y][next_x].is_some()
+                && !reached[next_y][next_x]
+            {
+                reached[next_y][next_x] = true;
+                pending.push((next_x, next_y));
+            }
+        }
+        if let Some((next_x, next_y)) = partner((x, y))
+            && !reached[next_y][next_x]
+        {
+            reached[next_y][next_x] = true;
+            pending.push((next_x, next_y));
+        }
+    }
+
+    for (y, row) in tiles.iter_mut().enumerate() {
+        for (x, tile) in row.iter_mut().enumerate() {
+            if matches!(tile, Some(MapEntity::F)) && !reached[y][x] {
+                *tile = None;
+            }
+        }
+    }
+
+    let open = |x: usize, y: usize| tiles[y][x].is_some();
+    let min_x = (0..cols)
+        .find(|x| (0..rows).any(|y| open(*x, y)))
+        .unwrap_or(player.0);
+    let max_x = (0..cols)
+        .rfind(|x| (0..rows).any(|y| open(*x, y)))
+        .unwrap_or(player.0);
+    let min_y = (0..rows)
+        .find(|y| (0..cols).any(|x| open(x, *y)))
+        .unwrap_or(player.1);
+    let max_y = (0..rows)
+        .rfind(|y| (0..cols).any(|x| open(x, *y)))
+        .unwrap_or(player.1);
+
+    let level_cols = max_x - min_x + 1;
+    let level_rows = max_y - min_y + 1;
+    if level_cols > MAX_MAP_COLS || level_rows > MAX_MAP_ROWS {
+        return Err(XsbError::TooLarge {
+            cols: level_cols,
+            rows: level_rows,
+        });
+    }
+
+    let width = level_cols.max(MAP_COLS);
+    let height = level_rows.max(MAP_ROWS);
+    let offset_x = (width - level_cols) / 2;
+    let offset_y = (height - level_rows) / 2;
+    let mut map = Map::new(width, height, MapEntity::V);
+
+    for (y, row) in tiles.iter().enumerate().take(max_y + 1).skip(min_y) {
+        for (x, tile) in row.iter().enumerate().take(max_x + 1).skip(min_x) {
+            let Some(entity) = *tile else {
+                continue;
+            };
+            let position = MapPosition::new(x - min_x + offset_x, y - min_y + offset_y);
+            map[&position] = entity;
+        }
+    }
+
+    let position =
+        |(x, y): (usize, usize)| MapPosition::new(x - min_x + offset_x, y - min_y + offset_y);
+    let mut state = LevelState::new(map, position(player));
+    for (pad, partner) in links {
+        state.link_teleporters(position(pad), position(partner));
+    }
+
+    Ok(state)
+}
+
+/// Every level found in a text with many levels, separated by titles, comments or blank lines
+pub(super) fn parse_collection(text: &str) -> Vec<Result<LevelState, XsbError>> {
+    let mut levels = vec![];
+    let mut block = String::new();
+
+    for line in text.lines() {
+        let row = line.trim_end();
+        if row.contains('#') && row.chars().all(|tile| XSB_TILES.contains(&tile)) {
+            block.push_str(row);
+            block.push('\n');
+        } else if !block.is_empty() {
+            levels.push(parse(&block));
+            block.clear();
+        }
+    }
+
+    if !block.is_empty() {
+        levels.push(parse(&block));
+    }
+
+    levels
+}
+
+/// Void next to the level is written as walls, since the edges of the map also stop the player.
+/// Only the first nine pairs of teleporters have a digit, the rest are written as floor.
+/// Plates and doors cannot be linked in the format, so they are written as floor and walls,
+/// and the colors of the boxes and zones are lost
+pub(super) fn write(state: &LevelState) -> String {
+    let width = state.map().width() as isize;
+    let height = state.map().height() as isize;
+    let open = |x: isize, y: isize| {
+        (0..width).contains(&x)
+            && (0..height).contains(&y)
+            && !matches!(
+                state.get_entity(&MapPosition::new(x as usize, y as usize)),
+                MapEntity::V
+            )
+    };
+
+    let cols = 0..width;
+    let rows = 0..height;
+    let min_x = cols.clone().find(|x| rows.clone().any(|y| open(*x, y)));
+    let max_x = cols.clone().rfind(|x| rows.clone().any(|y| open(*x, y)));
+    let min_y = rows.clone().find(|y| cols.clone().any(|x| open(x, *y)));
+    let max_y = rows.clone().rfind(|y| cols.clone().any(|x| open(x, *y)));
+    let (Some(min_x), Some(max_x), Some(min_y), Some(max_y)) = (min_x, max_x, min_y, max_y) else {
+        return String::new();
+    };
+
+    let character = state.character_position();
+    let mut pads = vec![];
+    for (pad, partner) in state.teleporters() {
+        if state.teleporter_partner(pad) == Some(*partner) && pads.len() < TELEPORTER_TILES {
+            pads.push((*pad, *partner));
+        }
+    }
+    let digit = |position: &MapPosition| {
+        pads.iter()
+            .position(|(pad, partner)| pad == position || partner == position)
+            .map_or(' ', |index| char::from(b'1' + index as u8))
+    };
+    let mut text = String::new();
+
+    for y in min_y - 1..=max_y + 1 {
+        let mut line = String::new();
+        for x in min_x - 1..=max_x + 1 {
+            let tile = if open(x, y) {
+                let position = MapPosition::new(x as usize, y as usize);
+                let has_character = position == character;
+                match state.get_entity(&position) {
+                    // the editor only places the character on the floor or on a zone
+                    MapEntity::F | MapEntity::I | MapEntity::T | MapEntity::S | MapEntity::D
+                        if has_character =>
+                    {
+                        '@'
+                    }
+                    MapEntity::Z if has_character => '+',
+                    MapEntity::F => ' ',
+                    MapEntity::Z => '.',
+                    MapEntity::B => '$',
+                    MapEntity::P => '*',
+                    MapEntity::I => '~',
+                    MapEntity::J => '=',
+                    // a box on a teleporter or on a plate is lost, the editor never places one there
+                    MapEntity::U | MapEntity::Q => '$',
+                    MapEntity::S => ' ',
+                    MapEntity::D => '#',
+                    MapEntity::H => '^',
+                    MapEntity::T => digit(&position),
+                    MapEntity::V => '#',
+                }
+            } else if (-1..=1).any(|dy| (-1..=1).any(|dx| open(x + dx, y + dy))) {
+                '#'
+            } else {
+                ' '
+            };
+            line.push(tile);
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+
+    text
+}
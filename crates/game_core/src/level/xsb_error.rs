@@ -0,0 +1,33 @@
+use std::fmt;
+
+use crate::map::{MAX_MAP_COLS, MAX_MAP_ROWS};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum XsbError {
+    Empty,
+    UnknownTile(char),
+    NoPlayer,
+    TooManyPlayers,
+    UnpairedTeleporter(char),
+    TooLarge { cols: usize, rows: usize },
+}
+
+impl fmt::Display for XsbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XsbError::Empty => write!(f, "the text contains no level"),
+            XsbError::UnknownTile(tile) => write!(f, "the tile '{tile}' is not supported"),
+            XsbError::NoPlayer => write!(f, "the level has no player"),
+            XsbError::TooManyPlayers => write!(f, "the level has more than one player"),
+            XsbError::UnpairedTeleporter(tile) => {
+                write!(f, "the teleporter '{tile}' does not appear exactly twice")
+            }
+            XsbError::TooLarge { cols, rows } => write!(
+                f,
+                "the level is {cols}x{rows}, larger than the {MAX_MAP_COLS}x{MAX_MAP_ROWS} limit"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for XsbError {}
@@ -1,5 +1,8 @@
 #![allow(clippy::module_inception)]
 
+pub mod generator;
 pub mod input;
 pub mod level;
 pub mod map;
+pub mod solver;
+pub mod versioned;
@@ -0,0 +1,216 @@
+use std::collections::VecDeque;
+
+use crate::input::Direction;
+
+use super::{Map, MapEntity, MapPosition};
+
+/// Static view of a map, indexed by cell, used by the analysis code
+pub(crate) struct Board {
+    cols: usize,
+    rows: usize,
+    walls: Vec<bool>,
+    zones: Vec<bool>,
+    ice: Vec<bool>,
+    teleporters: Vec<Option<usize>>,
+    doors: Vec<bool>,
+    holes: Vec<bool>,
+    /// Plates that open each door
+    plates: Vec<Vec<usize>>,
+}
+
+impl Board {
+    pub fn new(map: &Map) -> Board {
+        let mut walls = Vec::with_capacity(map.width() * map.height());
+        let mut zones = Vec::with_capacity(map.width() * map.height());
+        let mut ice = Vec::with_capacity(map.width() * map.height());
+        let mut doors = Vec::with_capacity(map.width() * map.height());
+        let mut holes = Vec::with_capacity(map.width() * map.height());
+
+        for position in map.positions() {
+            let entity = map[&position];
+            walls.push(matches!(entity, MapEntity::V));
+            zones.push(matches!(entity, MapEntity::Z | MapEntity::P));
+            ice.push(entity.is_ice());
+            doors.push(matches!(entity, MapEntity::D));
+            holes.push(matches!(entity, MapEntity::H));
+        }
+
+        Board {
+            cols: map.width(),
+            rows: map.height(),
+            walls,
+            zones,
+            ice,
+            teleporters: vec![None; map.width() * map.height()],
+            doors,
+            holes,
+            plates: vec![vec![]; map.width() * map.height()],
+        }
+    }
+
+    pub fn with_teleporters(mut self, links: &[(MapPosition, MapPosition)]) -> Board {
+        for (pad, partner) in links {
+            let (pad, partner) = (self.index(pad), self.index(partner));
+            self.teleporters[pad] = Some(partner);
+            self.teleporters[partner] = Some(pad);
+        }
+        self
+    }
+
+    pub fn with_switches(mut self, links: &[(MapPosition, MapPosition)]) -> Board {
+        for (plate, door) in links {
+            let (plate, door) = (self.index(plate), self.index(door));
+            self.plates[door].push(plate);
+        }
+        self
+    }
+
+    pub fn cells(&self) -> usize {
+        self.cols * self.rows
+    }
+
+    pub fn index(&self, position: &MapPosition) -> usize {
+        position.y() * self.cols + position.x()
+    }
+
+    pub fn position(&self, index: usize) -> MapPosition {
+        MapPosition::new(index % self.cols, index / self.cols)
+    }
+
+    pub fn is_wall(&self, index: usize) -> bool {
+        self.walls[index]
+    }
+
+    pub fn is_zone(&self, index: usize) -> bool {
+        self.zones[index]
+    }
+
+    /// Whether the map only has the tiles of the original game
+    pub fn is_classic(&self) -> bool {
+        !self.ice.contains(&true)
+            && self.teleporters.iter().all(Option::is_none)
+            && !self.doors.contains(&true)
+            && !self.holes.contains(&true)
+    }
+
+    pub fn is_door(&self, index: usize) -> bool {
+        self.doors[index]
+    }
+
+    /// Whether the character standing on the given cell can walk into the other one,
+    /// doors only let it in while one of their plates has the character or a sorted box on it
+    pub fn can_enter(&self, index: usize, from: usize, boxes: &[usize]) -> bool {
+        !self.doors[index]
+            || self.plates[index]
+                .iter()
+                .any(|plate| *plate == from || boxes.binary_search(plate).is_ok())
+    }
+
+    pub fn teleporter(&self, index: usize) -> Option<usize> {
+        self.teleporters[index]
+    }
+
+    pub fn boxes(&self, map: &Map) -> Vec<usize> {
+        (0..self.cells())
+            .filter(|index| map[&self.position(*index)].has_box())
+            .collect()
+    }
+
+    pub fn holes(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.cells()).filter(|index| self.holes[*index])
+    }
+
+    pub fn zones(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.cells()).filter(|index| self.zones[*index])
+    }
+
+    /// Returns the adjacent cell in the given direction, if it is inside the map
+    pub fn step(&self, index: usize, direction: &Direction) -> Option<usize> {
+        let x = index % self.cols;
+        let y = index / self.cols;
+        match direction {
+            Direction::Up if y > 0 => Some(index - self.cols),
+            Direction::Down if y < self.rows - 1 => Some(index + self.cols),
+            Direction::Left if x > 0 => Some(index - 1),
+            Direction::Right if x < self.cols - 1 => Some(index + 1),
+            _ => None,
+        }
+    }
+
+    /// Same as `step`, but walls are treated as the end of the map
+    pub fn open_step(&self, index: usize, direction: &Direction) -> Option<usize> {
+        self.step(index, direction)
+            .filter(|next_index| !self.walls[*next_index])
+    }
+
+    /// Cells where a box pulled in the given direction and the character pulling it end up,
+    /// the character starts next to the box and both of them move one cell
+    pub fn pull_box(&self, index: usize, direction: &Direction) -> Option<(usize, usize)> {
+        let from = self.open_step(index, direction)?;
+        let to = self.open_step(from, direction)?;
+        Some((from, to))
+    }
+
+    /// Open cells from where a box can never be pushed to any zone
+    pub fn dead_squares(&self) -> impl Iterator<Item = usize> + '_ {
+        let distances = self.pull(self.zones());
+        (0..self.cells()).filter(move |index| !self.walls[*index] && distances[*index].is_none())
+    }
+
+    /// Cell where something that has just moved into the given cell stops,
+    /// sliding over the ice until a wall, a door or one of the sorted boxes blocks it
+    pub fn slide(&self, index: usize, direction: &Direction, boxes: &[usize]) -> usize {
+        let mut current = index;
+        while self.ice[current] {
+            match self.open_step(current, direction) {
+                Some(next) if !self.doors[next] && boxes.binary_search(&next).is_err() => {
+                    current = next
+                }
+                _ => break,
+            }
+        }
+        current
+    }
+
+    /// Cell where something that has just moved into the given cell ends up,
+    /// after sliding and going through a teleporter not blocked by the sorted boxes
+    pub fn land(&self, index: usize, direction: &Direction, boxes: &[usize]) -> usize {
+        let index = self.slide(index, direction, boxes);
+        match self.teleporters[index] {
+            Some(partner) if boxes.binary_search(&partner).is_err() => partner,
+            _ => index,
+        }
+    }
+
+    /// Minimum number of pushes needed to take a box from each cell to the zone,
+    /// ignoring the rest of the boxes
+    pub fn push_distances_to(&self, zone: usize) -> Vec<Option<usize>> {
+        self.pull([zone].into_iter())
+    }
+
+    fn pull(&self, targets: impl Iterator<Item = usize>) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.cells()];
+        let mut queue = VecDeque::new();
+        for target in targets {
+            distances[target] = Some(0);
+            queue.push_back(target);
+        }
+
+        while let Some(index) = queue.pop_front() {
+            let distance = distances[index].unwrap_or_default();
+            for direction in Direction::ALL {
+                let Some(previous) = self.open_step(index, &direction.opposite()) else {
+                    continue;
+                };
+                if distances[previous].is_none()
+                    && self.open_step(previous, &direction.opposite()).is_some()
+                {
+                    distances[previous] = Some(distance + 1);
+                    queue.push_back(previous);
+                }
+            }
+        }
+
+        distances
+    }
+}
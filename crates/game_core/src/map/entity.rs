@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Default, Clone, Copy)]
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum MapEntity {
     #[default]
     /// Floor
@@ -13,4 +13,72 @@ pub enum MapEntity {
     P,
     /// Void
     V,
+    /// Ice
+    I,
+    /// Box in Ice
+    J,
+    /// Teleporter
+    T,
+    /// Box in Teleporter
+    U,
+    /// Pressure plate
+    S,
+    /// Box in pressure plate
+    Q,
+    /// Door, only open while one of its plates is pressed
+    D,
+    /// Hole, filled by the first box pushed into it
+    H,
+}
+
+impl MapEntity {
+    pub fn has_box(&self) -> bool {
+        matches!(
+            self,
+            MapEntity::B | MapEntity::P | MapEntity::J | MapEntity::U | MapEntity::Q
+        )
+    }
+
+    pub fn is_ice(&self) -> bool {
+        matches!(self, MapEntity::I | MapEntity::J)
+    }
+
+    pub fn is_teleporter(&self) -> bool {
+        matches!(self, MapEntity::T | MapEntity::U)
+    }
+
+    pub fn is_plate(&self) -> bool {
+        matches!(self, MapEntity::S | MapEntity::Q)
+    }
+
+    /// Whether the character or a box can move into it, doors are never free
+    /// since boxes cannot go through them
+    pub fn is_free(&self) -> bool {
+        matches!(
+            self,
+            MapEntity::F | MapEntity::Z | MapEntity::I | MapEntity::T | MapEntity::S
+        )
+    }
+
+    pub fn with_box(&self) -> MapEntity {
+        match self {
+            MapEntity::F => MapEntity::B,
+            MapEntity::Z => MapEntity::P,
+            MapEntity::I => MapEntity::J,
+            MapEntity::T => MapEntity::U,
+            MapEntity::S => MapEntity::Q,
+            entity => *entity,
+        }
+    }
+
+    pub fn without_box(&self) -> MapEntity {
+        match self {
+            MapEntity::B => MapEntity::F,
+            MapEntity::P => MapEntity::Z,
+            MapEntity::J => MapEntity::I,
+            MapEntity::U => MapEntity::T,
+            MapEntity::Q => MapEntity::S,
+            entity => *entity,
+        }
+    }
 }
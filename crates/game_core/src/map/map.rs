@@ -1,25 +1,159 @@
-use std::ops::{Index, IndexMut};
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
 
-use serde::{Deserialize, Serialize};
+use serde::{
+    Deserialize, Deserializer, Serialize,
+    de::{self, MapAccess, SeqAccess, Visitor},
+};
 
 use super::{MapEntity, MapPosition};
 
+/// Size of new maps, smaller levels are padded to it
 pub const MAP_COLS: usize = 10;
 pub const MAP_ROWS: usize = 10;
 
-#[derive(Serialize, Deserialize, Default, Clone, Copy)]
-pub struct Map([[MapEntity; MAP_COLS]; MAP_ROWS]);
+pub const MAX_MAP_COLS: usize = 50;
+pub const MAX_MAP_ROWS: usize = 50;
+
+const MAP_FIELDS: &[&str] = &["width", "height", "entities"];
+const LEGACY_MAP_SIZE: usize = 10;
+
+#[derive(Serialize, Clone)]
+pub struct Map {
+    width: usize,
+    height: usize,
+    entities: Vec<Vec<MapEntity>>,
+}
+
+impl Default for Map {
+    fn default() -> Self {
+        Map::new(MAP_COLS, MAP_ROWS, MapEntity::default())
+    }
+}
+
+impl Map {
+    pub fn new(width: usize, height: usize, entity: MapEntity) -> Map {
+        Map {
+            width,
+            height,
+            entities: vec![vec![entity; width]; height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: &MapPosition) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = MapPosition> + '_ {
+        (0..self.height).flat_map(|y| (0..self.width).map(move |x| MapPosition::new(x, y)))
+    }
+
+    /// Cuts or grows the map from its right and bottom edges, new cells are floor
+    pub fn resize(&mut self, width: usize, height: usize) {
+        for row in &mut self.entities {
+            row.resize(width, MapEntity::default());
+        }
+        self.entities
+            .resize(height, vec![MapEntity::default(); width]);
+        self.width = width;
+        self.height = height;
+    }
+}
 
 impl Index<&MapPosition> for Map {
     type Output = MapEntity;
 
     fn index(&self, index: &MapPosition) -> &Self::Output {
-        &self.0[index.y][index.x]
+        &self.entities[index.y][index.x]
     }
 }
 
 impl IndexMut<&MapPosition> for Map {
     fn index_mut(&mut self, index: &MapPosition) -> &mut Self::Output {
-        &mut self.0[index.y][index.x]
+        &mut self.entities[index.y][index.x]
+    }
+}
+
+/// Besides the sized format, level files from before maps had a size store
+/// the rows of a 10x10 map as a tuple
+impl<'de> Deserialize<'de> for Map {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(MapVisitor)
+    }
+}
+
+struct MapVisitor;
+
+impl<'de> Visitor<'de> for MapVisitor {
+    type Value = Map;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a map with its width, height and entities")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let rows: [[MapEntity; LEGACY_MAP_SIZE]; LEGACY_MAP_SIZE] = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+        Ok(Map {
+            width: LEGACY_MAP_SIZE,
+            height: LEGACY_MAP_SIZE,
+            entities: rows.iter().map(|row| row.to_vec()).collect(),
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut width = None;
+        let mut height = None;
+        let mut entities: Option<Vec<Vec<MapEntity>>> = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "width" => width = Some(map.next_value()?),
+                "height" => height = Some(map.next_value()?),
+                "entities" => entities = Some(map.next_value()?),
+                _ => return Err(de::Error::unknown_field(&key, MAP_FIELDS)),
+            }
+        }
+
+        let width = width.ok_or_else(|| de::Error::missing_field("width"))?;
+        let height = height.ok_or_else(|| de::Error::missing_field("height"))?;
+        let entities = entities.ok_or_else(|| de::Error::missing_field("entities"))?;
+
+        if width == 0
+            || height == 0
+            || entities.len() != height
+            || entities.iter().any(|row| row.len() != width)
+        {
+            return Err(de::Error::custom(format!(
+                "the entities do not fill a {width}x{height} map"
+            )));
+        }
+
+        Ok(Map {
+            width,
+            height,
+            entities,
+        })
     }
 }
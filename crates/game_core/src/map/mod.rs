@@ -1,7 +1,9 @@
+mod board;
 mod entity;
 mod map;
 mod position;
 
+pub(crate) use board::Board;
 pub use entity::MapEntity;
-pub use map::{MAP_COLS, MAP_ROWS, Map};
+pub use map::{MAP_COLS, MAP_ROWS, MAX_MAP_COLS, MAX_MAP_ROWS, Map};
 pub use position::MapPosition;
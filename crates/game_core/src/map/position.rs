@@ -2,9 +2,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::input::Direction;
 
-use super::{MAP_COLS, MAP_ROWS};
+use super::{MAP_COLS, MAP_ROWS, Map};
 
-#[derive(Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
 pub struct MapPosition {
     pub(super) x: usize,
     pub(super) y: usize,
@@ -14,24 +14,23 @@ impl Default for MapPosition {
     fn default() -> Self {
         Self {
             x: (MAP_COLS - 1) / 2,
-            y: (MAP_COLS - 1) / 2,
+            y: (MAP_ROWS - 1) / 2,
         }
     }
 }
 
 impl MapPosition {
     pub fn new(x: usize, y: usize) -> MapPosition {
-        assert!(x < MAP_COLS);
-        assert!(y < MAP_ROWS);
         MapPosition { x, y }
     }
 
-    pub fn update(&mut self, direction: &Direction) {
+    /// Moves one step, without leaving the map
+    pub fn update(&mut self, direction: &Direction, map: &Map) {
         match direction {
             Direction::Up => self.decrement_y(),
             Direction::Left => self.decrement_x(),
-            Direction::Down => self.increment_y(),
-            Direction::Right => self.increment_x(),
+            Direction::Down => self.increment_y(map.height()),
+            Direction::Right => self.increment_x(map.width()),
         }
     }
 
@@ -43,14 +42,14 @@ impl MapPosition {
         self.y
     }
 
-    fn increment_x(&mut self) {
-        if self.x < MAP_COLS - 1 {
+    fn increment_x(&mut self, cols: usize) {
+        if self.x + 1 < cols {
             self.x += 1;
         }
     }
 
-    fn increment_y(&mut self) {
-        if self.y < MAP_ROWS - 1 {
+    fn increment_y(&mut self, rows: usize) {
+        if self.y + 1 < rows {
             self.y += 1;
         }
     }
@@ -0,0 +1,20 @@
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SolverError {
+    Unsolvable,
+    NodeLimitReached,
+    TimeLimitReached,
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolverError::Unsolvable => write!(f, "the level has no solution"),
+            SolverError::NodeLimitReached => write!(f, "the node limit was reached"),
+            SolverError::TimeLimitReached => write!(f, "the time limit was reached"),
+        }
+    }
+}
+
+impl std::error::Error for SolverError {}
@@ -0,0 +1,77 @@
+const UNREACHABLE: i64 = i64::MAX / 4;
+
+/// Cheapest way of assigning a different column to every row, where `None`
+/// marks a forbidden pair. Rows must not outnumber columns.
+pub(super) fn minimum_matching(costs: &[Vec<Option<usize>>]) -> Option<usize> {
+    let rows = costs.len();
+    let columns = costs.first().map_or(0, Vec::len);
+    if rows == 0 {
+        return Some(0);
+    }
+
+    // Hungarian algorithm, with 1-based rows and columns so that 0 can act as a sentinel
+    let mut row_potentials = vec![0; rows + 1];
+    let mut column_potentials = vec![0; columns + 1];
+    let mut assigned_rows = vec![0; columns + 1];
+    let mut previous_columns = vec![0; columns + 1];
+
+    for row in 1..=rows {
+        assigned_rows[0] = row;
+        let mut column = 0;
+        let mut slack = vec![UNREACHABLE * 2; columns + 1];
+        let mut used = vec![false; columns + 1];
+
+        loop {
+            used[column] = true;
+            let current_row = assigned_rows[column];
+            let mut delta = UNREACHABLE * 2;
+            let mut next_column = 0;
+
+            for candidate in 1..=columns {
+                if used[candidate] {
+                    continue;
+                }
+                let cost =
+                    costs[current_row - 1][candidate - 1].map_or(UNREACHABLE, |cost| cost as i64);
+                let reduced = cost - row_potentials[current_row] - column_potentials[candidate];
+                if reduced < slack[candidate] {
+                    slack[candidate] = reduced;
+                    previous_columns[candidate] = column;
+                }
+                if slack[candidate] < delta {
+                    delta = slack[candidate];
+                    next_column = candidate;
+                }
+            }
+
+            for candidate in 0..=columns {
+                if used[candidate] {
+                    row_potentials[assigned_rows[candidate]] += delta;
+                    column_potentials[candidate] -= delta;
+                } else {
+                    slack[candidate] -= delta;
+                }
+            }
+
+            column = next_column;
+            if assigned_rows[column] == 0 {
+                break;
+            }
+        }
+
+        while column != 0 {
+            let previous = previous_columns[column];
+            assigned_rows[column] = assigned_rows[previous];
+            column = previous;
+        }
+    }
+
+    let total: i64 = (1..=columns)
+        .filter(|column| assigned_rows[*column] != 0)
+        .map(|column| {
+            costs[assigned_rows[column] - 1][column - 1].map_or(UNREACHABLE, |cost| cost as i64)
+        })
+        .sum();
+
+    (total < UNREACHABLE).then_some(total as usize)
+}
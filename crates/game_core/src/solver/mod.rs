@@ -0,0 +1,13 @@
+mod error;
+mod matching;
+mod mode;
+mod push_path;
+mod solution;
+mod solver;
+
+pub(crate) use push_path::find_push_path;
+
+pub use error::SolverError;
+pub use mode::SolverMode;
+pub use solution::Solution;
+pub use solver::Solver;
@@ -0,0 +1,17 @@
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SolverMode {
+    /// Fewest moves, ties broken by pushes
+    #[default]
+    Moves,
+    /// Fewest pushes, ties broken by moves
+    Pushes,
+}
+
+impl SolverMode {
+    pub(super) fn priority(self, moves: usize, pushes: usize) -> (usize, usize) {
+        match self {
+            SolverMode::Moves => (moves, pushes),
+            SolverMode::Pushes => (pushes, moves),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::{
+    input::Direction,
+    level::LevelState,
+    map::{Board, MapPosition},
+};
+
+use super::solver::{Node, path, walk};
+
+struct Entry {
+    node: Node,
+    /// Cell of the box being moved
+    box_index: usize,
+    parent: Option<(usize, Direction)>,
+}
+
+/// Directions that take one box to the target with the fewest pushes, walking around it
+/// when needed, the rest of the boxes stay where they are and no box falls into a hole
+pub(crate) fn find_push_path(
+    state: &LevelState,
+    box_position: &MapPosition,
+    target: &MapPosition,
+) -> Option<Vec<Direction>> {
+    let map = state.map();
+    if !map.contains(box_position) || !map.contains(target) || !map[box_position].has_box() {
+        return None;
+    }
+
+    let board = state.board();
+    let (start, target) = (board.index(box_position), board.index(target));
+    let root = Node::new(&board, state);
+    let mut visited = HashSet::from([(start, root.character)]);
+    let mut entries = vec![Entry {
+        node: root,
+        box_index: start,
+        parent: None,
+    }];
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(id) = queue.pop_front() {
+        if entries[id].box_index == target {
+            return Some(directions(&board, &entries, id));
+        }
+
+        let (node, box_index) = (entries[id].node.clone(), entries[id].box_index);
+        let slot = node.boxes.binary_search(&box_index).ok()?;
+        let steps = walk(&board, &node);
+        for direction in Direction::ALL {
+            if board
+                .open_step(box_index, &direction.opposite())
+                .and_then(|behind| steps[behind])
+                .is_none()
+            {
+                continue;
+            }
+            let Some((child, Some(landed))) = node.push(&board, slot, &direction) else {
+                continue;
+            };
+            if visited.insert((landed, child.character)) {
+                entries.push(Entry {
+                    node: child,
+                    box_index: landed,
+                    parent: Some((id, direction)),
+                });
+                queue.push_back(entries.len() - 1);
+            }
+        }
+    }
+
+    None
+}
+
+fn directions(board: &Board, entries: &[Entry], id: usize) -> Vec<Direction> {
+    let mut pushes = vec![];
+    let mut current = id;
+    while let Some((parent, direction)) = entries[current].parent {
+        pushes.push((parent, direction));
+        current = parent;
+    }
+    pushes.reverse();
+
+    let mut directions = vec![];
+    for (parent, direction) in pushes {
+        let entry = &entries[parent];
+        let behind = board
+            .step(entry.box_index, &direction.opposite())
+            .expect("The push must start inside the map");
+        directions.extend(path(&walk(board, &entry.node), behind));
+        directions.push(direction);
+    }
+    directions
+}
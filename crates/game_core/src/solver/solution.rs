@@ -0,0 +1,57 @@
+use crate::input::{Direction, Input};
+
+#[derive(Default, Clone)]
+pub struct Solution {
+    directions: Vec<Direction>,
+    pushes: usize,
+    expanded: usize,
+    generated: usize,
+}
+
+impl Solution {
+    pub(super) fn new(
+        directions: Vec<Direction>,
+        pushes: usize,
+        expanded: usize,
+        generated: usize,
+    ) -> Solution {
+        Solution {
+            directions,
+            pushes,
+            expanded,
+            generated,
+        }
+    }
+
+    pub fn directions(&self) -> &[Direction] {
+        &self.directions
+    }
+
+    pub fn inputs(&self) -> impl Iterator<Item = Input> + '_ {
+        self.directions
+            .iter()
+            .map(|direction| Input::Direction(*direction))
+    }
+
+    pub fn moves(&self) -> usize {
+        self.directions.len()
+    }
+
+    pub fn pushes(&self) -> usize {
+        self.pushes
+    }
+
+    /// Positions the solver had to look into before finding the solution
+    pub fn expanded(&self) -> usize {
+        self.expanded
+    }
+
+    /// Average of new positions found from each expanded position
+    pub fn branching_factor(&self) -> f64 {
+        if self.expanded == 0 {
+            0.0
+        } else {
+            self.generated as f64 / self.expanded as f64
+        }
+    }
+}
@@ -0,0 +1,407 @@
+use std::{
+    borrow::Cow,
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    input::Direction,
+    level::{LevelState, frozen_squares},
+    map::Board,
+};
+
+use super::{Solution, SolverError, SolverMode, matching::minimum_matching};
+
+const DEFAULT_MAX_NODES: usize = 1_000_000;
+const TIME_CHECK_INTERVAL: usize = 1024;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(super) struct Node {
+    pub character: usize,
+    pub boxes: Vec<usize>,
+    /// Color of each box, in the same order
+    pub colors: Vec<u8>,
+    /// Sorted holes that have not been filled yet
+    pub holes: Vec<usize>,
+}
+
+struct Entry {
+    node: Node,
+    moves: usize,
+    pushes: usize,
+    estimate: usize,
+    parent: Option<(usize, usize, Direction)>,
+}
+
+#[derive(Clone, Copy)]
+pub(super) struct Step {
+    distance: usize,
+    previous: Option<(usize, Direction)>,
+}
+
+pub struct Solver {
+    mode: SolverMode,
+    max_nodes: usize,
+    max_time: Option<Duration>,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self {
+            mode: SolverMode::default(),
+            max_nodes: DEFAULT_MAX_NODES,
+            max_time: None,
+        }
+    }
+}
+
+impl Solver {
+    pub fn new(mode: SolverMode) -> Solver {
+        Solver {
+            mode,
+            ..Solver::default()
+        }
+    }
+
+    pub fn max_nodes(mut self, max_nodes: usize) -> Solver {
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    /// The time limit relies on `Instant`, which is not available on the web
+    pub fn max_time(mut self, max_time: Duration) -> Solver {
+        self.max_time = Some(max_time);
+        self
+    }
+
+    pub fn solve(&self, state: &LevelState) -> Result<Solution, SolverError> {
+        let started = self.max_time.map(|_| Instant::now());
+        let board = state.board();
+        let zones: Vec<(usize, u8)> = board
+            .zones()
+            .map(|zone| (zone, state.zone_color(&board.position(zone))))
+            .collect();
+        let root = Node::new(&board, state);
+
+        if root.boxes.len() < zones.len() {
+            return Err(SolverError::Unsolvable);
+        }
+
+        let spare = root.boxes.len() - zones.len();
+        let distances: Vec<Vec<Option<usize>>> = zones
+            .iter()
+            .map(|(zone, _)| board.push_distances_to(*zone))
+            .collect();
+        // the push distances and the frozen boxes assume that boxes stop right after
+        // being pushed and can go anywhere that is not a wall, which is not true
+        // on the ice, on the teleporters or with doors
+        let classic = board.is_classic();
+        let lower_bound = |node: &Node| {
+            if classic {
+                estimate(&distances, &zones, node)
+            } else {
+                Some(0)
+            }
+        };
+        let Some(root_estimate) = lower_bound(&root) else {
+            return Err(SolverError::Unsolvable);
+        };
+
+        let mut entries = vec![Entry {
+            node: root.clone(),
+            moves: 0,
+            pushes: 0,
+            estimate: root_estimate,
+            parent: None,
+        }];
+        let mut indices = HashMap::from([(self.key(&board, &root), 0)]);
+        let mut queue = BinaryHeap::from([Reverse(((root_estimate, 0), 0))]);
+        let mut expanded = 0;
+        let mut generated = 0;
+
+        while let Some(Reverse((priority, id))) = queue.pop() {
+            let entry = &entries[id];
+            if priority != self.priority(entry) {
+                continue;
+            }
+
+            if zones.iter().all(|(zone, color)| {
+                entry
+                    .node
+                    .boxes
+                    .binary_search(zone)
+                    .is_ok_and(|slot| entry.node.colors[slot] == *color)
+            }) {
+                return Ok(Self::solution(&board, &entries, id, expanded, generated));
+            }
+
+            expanded += 1;
+            if expanded > self.max_nodes {
+                return Err(SolverError::NodeLimitReached);
+            }
+            if expanded % TIME_CHECK_INTERVAL == 0
+                && started
+                    .zip(self.max_time)
+                    .is_some_and(|(started, max_time)| started.elapsed() > max_time)
+            {
+                return Err(SolverError::TimeLimitReached);
+            }
+
+            let node = entry.node.clone();
+            let (moves, pushes) = (entry.moves, entry.pushes);
+            let steps = walk(&board, &node);
+
+            for (slot, box_index) in node.boxes.iter().enumerate() {
+                for direction in Direction::ALL {
+                    let Some(Some(step)) = board
+                        .open_step(*box_index, &direction.opposite())
+                        .map(|behind| steps[behind])
+                    else {
+                        continue;
+                    };
+                    let Some((child_node, landed)) = node.push(&board, slot, &direction) else {
+                        continue;
+                    };
+
+                    let Some(child_estimate) = lower_bound(&child_node) else {
+                        continue;
+                    };
+                    if classic
+                        && spare == 0
+                        && landed.is_some_and(|landed| {
+                            frozen_squares(&board, &child_node.boxes, landed)
+                                .next()
+                                .is_some()
+                        })
+                    {
+                        continue;
+                    }
+                    let child = Entry {
+                        node: child_node,
+                        moves: moves + step.distance + 1,
+                        pushes: pushes + 1,
+                        estimate: child_estimate,
+                        parent: Some((id, *box_index, direction)),
+                    };
+                    let child_priority = self.priority(&child);
+
+                    let key = self.key(&board, &child.node);
+                    let child_id = match indices.get(&key) {
+                        Some(&child_id) => {
+                            if self.priority(&entries[child_id]) <= child_priority {
+                                continue;
+                            }
+                            entries[child_id] = child;
+                            child_id
+                        }
+                        None => {
+                            indices.insert(key, entries.len());
+                            entries.push(child);
+                            entries.len() - 1
+                        }
+                    };
+
+                    queue.push(Reverse((child_priority, child_id)));
+                    generated += 1;
+                }
+            }
+        }
+
+        Err(SolverError::Unsolvable)
+    }
+
+    /// When only pushes matter, every position the character can walk to is equivalent
+    fn key(&self, board: &Board, node: &Node) -> Node {
+        match self.mode {
+            SolverMode::Moves => node.clone(),
+            // sliding can take the character somewhere it cannot walk back from
+            SolverMode::Pushes if !board.is_classic() => node.clone(),
+            SolverMode::Pushes => Node {
+                character: walk(board, node)
+                    .iter()
+                    .position(Option::is_some)
+                    .unwrap_or(node.character),
+                boxes: node.boxes.clone(),
+                colors: node.colors.clone(),
+                holes: node.holes.clone(),
+            },
+        }
+    }
+
+    fn priority(&self, entry: &Entry) -> (usize, usize) {
+        let (primary, secondary) = self.mode.priority(entry.moves, entry.pushes);
+        (primary + entry.estimate, secondary)
+    }
+
+    fn solution(
+        board: &Board,
+        entries: &[Entry],
+        id: usize,
+        expanded: usize,
+        generated: usize,
+    ) -> Solution {
+        let mut pushes = vec![];
+        let mut current = id;
+        while let Some((parent, box_index, direction)) = entries[current].parent {
+            pushes.push((box_index, direction));
+            current = parent;
+        }
+        pushes.reverse();
+
+        let mut node = entries[current].node.clone();
+        let mut directions = vec![];
+
+        for (box_index, direction) in &pushes {
+            let behind = board
+                .step(*box_index, &direction.opposite())
+                .expect("The push must start inside the map");
+            let steps = walk(board, &node);
+            directions.extend(path(&steps, behind));
+            directions.push(*direction);
+
+            let slot = node
+                .boxes
+                .binary_search(box_index)
+                .expect("The pushed box must exist");
+            (node, _) = node
+                .push(board, slot, direction)
+                .expect("The push must be possible");
+        }
+
+        Solution::new(directions, pushes.len(), expanded, generated)
+    }
+}
+
+impl Node {
+    pub fn new(board: &Board, state: &LevelState) -> Node {
+        let boxes = board.boxes(state.map());
+        Node {
+            character: board.index(&state.character_position()),
+            colors: boxes
+                .iter()
+                .map(|index| state.box_color(&board.position(*index)))
+                .collect(),
+            boxes,
+            holes: board.holes().collect(),
+        }
+    }
+
+    /// Node after pushing the box in the given slot, and the cell where the box ends up
+    /// unless it fell into a hole
+    pub fn push(
+        &self,
+        board: &Board,
+        slot: usize,
+        direction: &Direction,
+    ) -> Option<(Node, Option<usize>)> {
+        let box_index = self.boxes[slot];
+        let target = board.open_step(box_index, direction)?;
+        if board.is_door(target) || self.boxes.binary_search(&target).is_ok() {
+            return None;
+        }
+
+        let mut node = self.clone();
+        let landed = match self.holes.binary_search(&target) {
+            Ok(hole) => {
+                node.boxes.remove(slot);
+                node.colors.remove(slot);
+                node.holes.remove(hole);
+                None
+            }
+            Err(_) => {
+                // the pushed box is still in place, where the character is moving into
+                let landed = board.land(target, direction, &self.obstacles());
+                node.move_box(slot, landed);
+                Some(landed)
+            }
+        };
+        node.character = board.land(box_index, direction, &node.obstacles());
+
+        Some((node, landed))
+    }
+
+    /// Sorted cells that block the character and stop the boxes
+    fn obstacles(&self) -> Cow<'_, [usize]> {
+        if self.holes.is_empty() {
+            return Cow::Borrowed(&self.boxes);
+        }
+        let mut obstacles = [self.boxes.as_slice(), self.holes.as_slice()].concat();
+        obstacles.sort_unstable();
+        Cow::Owned(obstacles)
+    }
+
+    /// Keeps the boxes sorted, with their colors next to them
+    fn move_box(&mut self, slot: usize, target: usize) {
+        self.boxes.remove(slot);
+        let color = self.colors.remove(slot);
+        let slot = self.boxes.partition_point(|index| *index < target);
+        self.boxes.insert(slot, target);
+        self.colors.insert(slot, color);
+    }
+}
+
+/// Lower bound of the pushes left, pairing every zone with a different box of its color
+fn estimate(distances: &[Vec<Option<usize>>], zones: &[(usize, u8)], node: &Node) -> Option<usize> {
+    let costs: Vec<Vec<Option<usize>>> = distances
+        .iter()
+        .zip(zones)
+        .map(|(zone_distances, (_, zone_color))| {
+            node.boxes
+                .iter()
+                .zip(&node.colors)
+                .map(|(index, color)| zone_distances[*index].filter(|_| color == zone_color))
+                .collect()
+        })
+        .collect();
+    minimum_matching(&costs)
+}
+
+pub(super) fn walk(board: &Board, node: &Node) -> Vec<Option<Step>> {
+    let obstacles = node.obstacles();
+    let mut steps = vec![None; board.cells()];
+    let mut queue = VecDeque::from([node.character]);
+    steps[node.character] = Some(Step {
+        distance: 0,
+        previous: None,
+    });
+
+    while let Some(index) = queue.pop_front() {
+        let distance = steps[index].map_or(0, |step| step.distance);
+        for direction in Direction::ALL {
+            let Some(next_index) = board.open_step(index, &direction) else {
+                continue;
+            };
+            if obstacles.binary_search(&next_index).is_ok()
+                || !board.can_enter(next_index, index, &node.boxes)
+            {
+                continue;
+            }
+            let next_index = board.land(next_index, &direction, &obstacles);
+            if steps[next_index].is_none() {
+                steps[next_index] = Some(Step {
+                    distance: distance + 1,
+                    previous: Some((index, direction)),
+                });
+                queue.push_back(next_index);
+            }
+        }
+    }
+
+    steps
+}
+
+pub(super) fn path(steps: &[Option<Step>], to: usize) -> Vec<Direction> {
+    let mut directions = vec![];
+    let mut current = to;
+    while let Some(Some(Step {
+        previous: Some((previous, direction)),
+        ..
+    })) = steps.get(current)
+    {
+        directions.push(*direction);
+        current = *previous;
+    }
+    directions.reverse();
+    directions
+}
@@ -0,0 +1,20 @@
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VersionError {
+    Newer { version: u32, supported: u32 },
+}
+
+impl fmt::Display for VersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionError::Newer { version, supported } => write!(
+                f,
+                "the file has the format version {version}, but this version of the game only \
+                 reads up to {supported}, update the game to open it"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VersionError {}
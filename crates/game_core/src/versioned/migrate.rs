@@ -0,0 +1,12 @@
+use serde::Deserializer;
+
+/// Data saved to files, which keeps reading the files written by older versions
+pub trait Migrate: Sized {
+    /// Version of the format written by this build, files without any version are 0.
+    /// It goes up whenever the data changes in a way serde defaults can not cover
+    const VERSION: u32;
+
+    /// Reads data written with an older or the current version of the format, each
+    /// version is read as it was written and upgraded step by step to the current one
+    fn migrate<'de, D: Deserializer<'de>>(version: u32, deserializer: D) -> Result<Self, D::Error>;
+}
@@ -0,0 +1,7 @@
+mod error;
+mod migrate;
+mod versioned;
+
+pub use error::VersionError;
+pub use migrate::Migrate;
+pub use versioned::{deserialize, serialize};
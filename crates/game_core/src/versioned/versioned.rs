@@ -0,0 +1,224 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{
+        self, DeserializeSeed, IntoDeserializer, MapAccess, Visitor, value::MapAccessDeserializer,
+    },
+    ser::SerializeStruct,
+};
+
+use super::{error::VersionError, migrate::Migrate};
+
+const NAME: &str = "Versioned";
+const FIELDS: &[&str] = &["version", "data"];
+
+/// Writes the data in an envelope with the version of its format,
+/// to be used as `#[serde(with = "game_core::versioned")]`
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Migrate + Serialize,
+    S: Serializer,
+{
+    let mut envelope = serializer.serialize_struct(NAME, FIELDS.len())?;
+    envelope.serialize_field("version", &T::VERSION)?;
+    envelope.serialize_field("data", value)?;
+    envelope.end()
+}
+
+/// Reads the envelope written by `serialize`, or the bare data written before there were
+/// versions, and migrates it to the current version. Newer versions are an error
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Migrate,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_struct(NAME, FIELDS, EnvelopeVisitor(PhantomData))
+}
+
+struct EnvelopeVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Migrate> Visitor<'de> for EnvelopeVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a struct with the version of its format")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+        match map.next_key::<Key>()? {
+            Some(Key(key)) if key == "version" => (),
+            // the struct is the bare data, so its first field has to be read again
+            key => {
+                let map = Prepended {
+                    key: key.map(|Key(key)| key),
+                    map,
+                };
+                return T::migrate(0, MapAccessDeserializer::new(map));
+            }
+        }
+
+        let version: u32 = map.next_value()?;
+        if version > T::VERSION {
+            return Err(de::Error::custom(VersionError::Newer {
+                version,
+                supported: T::VERSION,
+            }));
+        }
+
+        match map.next_key::<Key>()? {
+            Some(Key(next)) if next == "data" => (),
+            Some(Key(next)) => return Err(de::Error::unknown_field(&next, FIELDS)),
+            None => return Err(de::Error::missing_field("data")),
+        }
+        let value = map.next_value_seed(MigrationSeed {
+            version,
+            data: PhantomData,
+        })?;
+
+        match map.next_key::<Key>()? {
+            Some(Key(next)) => Err(de::Error::custom(format!(
+                "unexpected field `{next}` after the data"
+            ))),
+            None => Ok(value),
+        }
+    }
+}
+
+struct MigrationSeed<T> {
+    version: u32,
+    data: PhantomData<T>,
+}
+
+impl<'de, T: Migrate> DeserializeSeed<'de> for MigrationSeed<T> {
+    type Value = T;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        T::migrate(self.version, deserializer)
+    }
+}
+
+struct Key(String);
+
+impl<'de> Deserialize<'de> for Key {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Key, D::Error> {
+        struct KeyVisitor;
+
+        impl Visitor<'_> for KeyVisitor {
+            type Value = Key;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a field name")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Key, E> {
+                Ok(Key(value.to_string()))
+            }
+        }
+
+        deserializer.deserialize_identifier(KeyVisitor)
+    }
+}
+
+/// Map that gives back a key that was already read before the rest of it
+struct Prepended<A> {
+    key: Option<String>,
+    map: A,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for Prepended<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error> {
+        match self.key.take() {
+            Some(key) => seed
+                .deserialize(IntoDeserializer::<A::Error>::into_deserializer(key))
+                .map(Some),
+            None => self.map.next_key_seed(seed),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, A::Error> {
+        self.map.next_value_seed(seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Deserializer, Serialize};
+
+    use super::super::{error::VersionError, migrate::Migrate};
+
+    /// Format whose version 2 renamed `volume` to `music_volume`
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Options {
+        music_volume: f64,
+    }
+
+    impl Migrate for Options {
+        const VERSION: u32 = 2;
+
+        fn migrate<'de, D: Deserializer<'de>>(
+            version: u32,
+            deserializer: D,
+        ) -> Result<Options, D::Error> {
+            #[derive(Deserialize)]
+            struct OptionsV1 {
+                volume: f64,
+            }
+
+            match version {
+                0 | 1 => OptionsV1::deserialize(deserializer).map(|options| Options {
+                    music_volume: options.volume,
+                }),
+                2 => Options::deserialize(deserializer),
+                _ => unreachable!("Newer versions are rejected before the migration"),
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct OptionsFile(#[serde(with = "crate::versioned")] Options);
+
+    fn read(text: &str) -> Result<Options, ron::error::SpannedError> {
+        ron::from_str::<OptionsFile>(text).map(|OptionsFile(options)| options)
+    }
+
+    #[test]
+    fn migrate_bare_data() {
+        assert_eq!(read("((volume: 0.5))"), Ok(Options { music_volume: 0.5 }));
+    }
+
+    #[test]
+    fn migrate_version_1() {
+        assert_eq!(
+            read("((version: 1, data: (volume: 0.5)))"),
+            Ok(Options { music_volume: 0.5 })
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let text = ron::to_string(&OptionsFile(Options { music_volume: 0.5 })).unwrap();
+        assert_eq!(text, "((version:2,data:(music_volume:0.5)))");
+        assert_eq!(read(&text), Ok(Options { music_volume: 0.5 }));
+    }
+
+    #[test]
+    fn reject_newer_version() {
+        let error = read("((version: 3, data: (music_volume: 0.5)))").unwrap_err();
+        let newer = VersionError::Newer {
+            version: 3,
+            supported: 2,
+        };
+        assert_eq!(error.code, ron::Error::Message(newer.to_string()));
+    }
+
+    #[test]
+    fn reject_fields_after_the_data() {
+        assert!(read("((version: 2, data: (music_volume: 0.5), extra: 1))").is_err());
+    }
+}
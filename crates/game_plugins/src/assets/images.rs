@@ -18,6 +18,33 @@ pub struct Images {
     #[asset(path = "images/entities/zone.png")]
     #[asset(image(sampler(filter = nearest)))]
     pub entity_zone: Handle<Image>,
+    #[asset(path = "images/entities/ice.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub entity_ice: Handle<Image>,
+    #[asset(path = "images/entities/box_on_ice.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub entity_box_on_ice: Handle<Image>,
+    #[asset(path = "images/entities/teleporter.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub entity_teleporter: Handle<Image>,
+    #[asset(path = "images/entities/box_on_teleporter.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub entity_box_on_teleporter: Handle<Image>,
+    #[asset(path = "images/entities/plate.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub entity_plate: Handle<Image>,
+    #[asset(path = "images/entities/box_on_plate.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub entity_box_on_plate: Handle<Image>,
+    #[asset(path = "images/entities/door.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub entity_door: Handle<Image>,
+    #[asset(path = "images/entities/open_door.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub entity_open_door: Handle<Image>,
+    #[asset(path = "images/entities/hole.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub entity_hole: Handle<Image>,
     #[asset(path = "images/brushes/box.png")]
     #[asset(image(sampler(filter = nearest)))]
     pub brush_box: Handle<Image>,
@@ -33,6 +60,24 @@ pub struct Images {
     #[asset(path = "images/brushes/zone.png")]
     #[asset(image(sampler(filter = nearest)))]
     pub brush_zone: Handle<Image>,
+    #[asset(path = "images/brushes/ice.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub brush_ice: Handle<Image>,
+    #[asset(path = "images/brushes/box_on_ice.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub brush_box_on_ice: Handle<Image>,
+    #[asset(path = "images/brushes/teleporter.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub brush_teleporter: Handle<Image>,
+    #[asset(path = "images/brushes/plate.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub brush_plate: Handle<Image>,
+    #[asset(path = "images/brushes/door.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub brush_door: Handle<Image>,
+    #[asset(path = "images/brushes/hole.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub brush_hole: Handle<Image>,
     #[asset(path = "images/brushes/character.png")]
     #[asset(image(sampler(filter = nearest)))]
     pub brush_character: Handle<Image>,
@@ -109,7 +109,7 @@ fn play_music(
         music
             .play(match game_state.get() {
                 GameState::Title | GameState::Instructions => sounds.music_title.clone(),
-                GameState::Selection(_) | GameState::Options | GameState::Limit => {
+                GameState::Selection(_) | GameState::Options | GameState::Import => {
                     sounds.music_selection.clone()
                 }
                 GameState::Level | GameState::Editor => sounds.music_level.clone(),
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+/// Clipboard of the system, kept open since some systems lose the copied text once it
+/// closes. Browsers only give access to it asynchronously, so there it is always empty
+pub struct Clipboard {
+    #[cfg(not(target_family = "wasm"))]
+    inner: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    pub fn open() -> Clipboard {
+        Clipboard {
+            #[cfg(not(target_family = "wasm"))]
+            inner: arboard::Clipboard::new()
+                .inspect_err(|error| warn!("Cannot open the clipboard: {error}"))
+                .ok(),
+        }
+    }
+
+    pub fn get_text(&mut self) -> Option<String> {
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(clipboard) = &mut self.inner {
+            match clipboard.get_text() {
+                Ok(text) => return Some(text),
+                Err(error) => warn!("Cannot paste from the clipboard: {error}"),
+            }
+        }
+        None
+    }
+
+    /// Whether the text was copied
+    pub fn set_text(&mut self, text: &str) -> bool {
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(clipboard) = &mut self.inner {
+            match clipboard.set_text(text) {
+                Ok(()) => return true,
+                Err(error) => warn!("Cannot copy to the clipboard: {error}"),
+            }
+        }
+        false
+    }
+}
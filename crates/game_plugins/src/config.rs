@@ -6,6 +6,7 @@ use bevy::{
 };
 
 use crate::{
+    clipboard::Clipboard,
     input::{self, InputEvent},
     level::{self, LevelInsertionEvent},
     save_file,
@@ -16,6 +17,8 @@ pub struct Plugin;
 
 impl BevyPlugin for Plugin {
     fn build(&self, app: &mut App) {
+        save_file::register_data_source(app);
+
         app.add_plugins((
             DefaultPlugins
                 .set(AssetPlugin {
@@ -38,6 +41,7 @@ impl BevyPlugin for Plugin {
             AudioPlugin,
         ))
         .insert_resource(ClearColor(crate::theme::DARK))
+        .insert_non_send_resource(Clipboard::open())
         .init_state::<GameState>()
         .add_event::<InputEvent>()
         .add_event::<GameStateTransitionEvent>()
@@ -29,10 +29,17 @@ fn gather_input(
                 KeyCode::ArrowLeft => Input::left(),
                 KeyCode::ArrowRight => Input::right(),
                 KeyCode::KeyZ => Input::undo(),
+                KeyCode::KeyY => Input::redo(),
+                KeyCode::KeyX => Input::undo_push(),
+                KeyCode::Home => Input::undo_all(),
                 KeyCode::F5 => Input::reload(),
                 KeyCode::Escape => Input::exit(),
                 KeyCode::Space => Input::select(),
                 KeyCode::Enter => Input::toggle(),
+                KeyCode::KeyC => Input::color(),
+                KeyCode::KeyM => Input::mode(),
+                KeyCode::KeyE => Input::export(),
+                KeyCode::KeyI => Input::import(),
                 KeyCode::Delete => Input::delete(),
                 _ => return,
             };
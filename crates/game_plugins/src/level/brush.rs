@@ -1,22 +1,9 @@
 use bevy::prelude::*;
 
-use game_core::map::MapPosition;
+use game_core::{level::COLORS, map::MapPosition};
 
 use crate::{assets::prelude::Images, level::apply_position_to_translation};
 
-#[derive(Default, Resource)]
-pub struct LevelValidity {
-    pub zones: usize,
-    pub boxes: usize,
-}
-
-impl LevelValidity {
-    pub fn reset(&mut self) {
-        self.zones = 0;
-        self.boxes = 0;
-    }
-}
-
 #[derive(Default)]
 pub enum BrushEntity {
     #[default]
@@ -25,6 +12,12 @@ pub enum BrushEntity {
     Zone,
     BoxInFloor,
     BoxInZone,
+    Ice,
+    BoxInIce,
+    Teleporter,
+    Plate,
+    Door,
+    Hole,
     Character,
 }
 
@@ -32,6 +25,10 @@ pub enum BrushEntity {
 pub struct Brush {
     pub entity: BrushEntity,
     pub position: MapPosition,
+    /// Last plate placed, new doors are linked to it
+    pub plate: Option<MapPosition>,
+    /// Color given to the boxes and zones that are painted
+    pub color: u8,
     pub blink_timer: Timer,
 }
 
@@ -43,6 +40,8 @@ impl Default for Brush {
         Self {
             entity: BrushEntity::default(),
             position: MapPosition::default(),
+            plate: None,
+            color: 0,
             blink_timer: Timer::from_seconds(0.1, TimerMode::Repeating),
         }
     }
@@ -74,8 +73,18 @@ impl Brush {
             BrushEntity::Void => BrushEntity::Zone,
             BrushEntity::Zone => BrushEntity::BoxInFloor,
             BrushEntity::BoxInFloor => BrushEntity::BoxInZone,
-            BrushEntity::BoxInZone => BrushEntity::Character,
+            BrushEntity::BoxInZone => BrushEntity::Ice,
+            BrushEntity::Ice => BrushEntity::BoxInIce,
+            BrushEntity::BoxInIce => BrushEntity::Teleporter,
+            BrushEntity::Teleporter => BrushEntity::Plate,
+            BrushEntity::Plate => BrushEntity::Door,
+            BrushEntity::Door => BrushEntity::Hole,
+            BrushEntity::Hole => BrushEntity::Character,
             BrushEntity::Character => BrushEntity::Floor,
         };
     }
+
+    pub fn cycle_color(&mut self) {
+        self.color = (self.color + 1) % COLORS;
+    }
 }
@@ -0,0 +1,72 @@
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future},
+};
+use game_core::{
+    level::{DifficultyLabel, LevelState},
+    solver::{Solver, SolverError},
+};
+use hashbrown::HashMap;
+
+use crate::level::handles::{LevelHandles, LevelStateAsset};
+
+const DIFFICULTY_MAX_NODES: usize = 50_000;
+
+/// Difficulty of every stock, custom and pack level, estimated in the background.
+/// Levels without a solution have no label
+#[derive(Resource, Default)]
+pub struct LevelDifficulties {
+    labels: HashMap<AssetId<LevelStateAsset>, Option<DifficultyLabel>>,
+    tasks: HashMap<AssetId<LevelStateAsset>, Task<Option<DifficultyLabel>>>,
+}
+
+impl LevelDifficulties {
+    pub fn get(&self, handle: &Handle<LevelStateAsset>) -> Option<DifficultyLabel> {
+        self.labels.get(&handle.id()).copied().flatten()
+    }
+}
+
+pub(super) fn estimate_difficulties(
+    mut difficulties: ResMut<LevelDifficulties>,
+    level_handles: Res<LevelHandles>,
+    level_states_assets: Res<Assets<LevelStateAsset>>,
+) {
+    let difficulties = difficulties.bypass_change_detection();
+
+    for handle in level_handles.iter() {
+        let id = handle.id();
+        if difficulties.labels.contains_key(&id) || difficulties.tasks.contains_key(&id) {
+            continue;
+        }
+        let Some(asset) = level_states_assets.get(id) else {
+            continue;
+        };
+
+        let state = LevelState::clone(asset);
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            // a level the solver gives up on is harder than any level it can finish
+            match state.difficulty(&Solver::default().max_nodes(DIFFICULTY_MAX_NODES)) {
+                Ok(difficulty) => Some(difficulty.label()),
+                Err(SolverError::NodeLimitReached | SolverError::TimeLimitReached) => {
+                    Some(DifficultyLabel::Expert)
+                }
+                Err(SolverError::Unsolvable) => None,
+            }
+        });
+        difficulties.tasks.insert(id, task);
+    }
+}
+
+pub(super) fn poll_difficulties(mut difficulties: ResMut<LevelDifficulties>) {
+    let mut finished = vec![];
+    for (id, task) in &mut difficulties.bypass_change_detection().tasks {
+        if let Some(label) = block_on(future::poll_once(task)) {
+            finished.push((*id, label));
+        }
+    }
+
+    for (id, label) in finished {
+        difficulties.tasks.remove(&id);
+        difficulties.labels.insert(id, label);
+    }
+}
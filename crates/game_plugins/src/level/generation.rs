@@ -0,0 +1,111 @@
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future},
+};
+use game_core::{
+    generator::{Generator, GeneratorError},
+    level::{LevelKind, LevelState, PlayMode},
+};
+
+use crate::level::{insertion::new_seed, resource::LevelResource};
+use crate::save_file::SaveFile;
+use crate::state::GameStateTransitionEvent;
+
+/// Seeds tried before giving up on a level
+const MAX_GENERATION_ATTEMPTS: usize = 8;
+
+/// Level generated in the background, the level scene starts once it is done
+#[derive(Resource, Default)]
+pub struct LevelGeneration {
+    task: Option<(u64, Task<Result<LevelState, GeneratorError>>)>,
+    attempts: usize,
+    failed: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GenerationStatus {
+    Idle,
+    Generating,
+    Failed,
+}
+
+impl LevelGeneration {
+    /// A level already being generated is replaced
+    pub(super) fn start(&mut self, seed: u64) {
+        self.attempts = 0;
+        self.failed = false;
+        self.spawn(seed);
+    }
+
+    /// Dropping the task cancels it
+    pub fn cancel(&mut self) {
+        self.task = None;
+        self.attempts = 0;
+        self.failed = false;
+    }
+
+    #[must_use]
+    pub fn status(&self) -> GenerationStatus {
+        if self.task.is_some() {
+            GenerationStatus::Generating
+        } else if self.failed {
+            GenerationStatus::Failed
+        } else {
+            GenerationStatus::Idle
+        }
+    }
+
+    fn spawn(&mut self, seed: u64) {
+        self.attempts += 1;
+        let task =
+            AsyncComputeTaskPool::get().spawn(async move { Generator::new(seed).generate() });
+        self.task = Some((seed, task));
+    }
+}
+
+pub(super) fn poll_generation(
+    mut commands: Commands,
+    mut generation: ResMut<LevelGeneration>,
+    mut scene_transition_event_writer: EventWriter<GameStateTransitionEvent>,
+    save_file: Res<SaveFile>,
+) {
+    let Some((seed, task)) = &mut generation.task else {
+        return;
+    };
+    let seed = *seed;
+    let Some(result) = block_on(future::poll_once(task)) else {
+        return;
+    };
+    generation.task = None;
+
+    match result {
+        Ok(state) => {
+            let level = LevelResource::new(
+                LevelKind::Generated(seed),
+                state,
+                save_file.get_undo_policy(),
+                PlayMode::default(),
+            );
+
+            commands.insert_resource(level);
+            scene_transition_event_writer.write(GameStateTransitionEvent::level());
+        }
+        // another seed is likely to work
+        Err(error) if generation.attempts < MAX_GENERATION_ATTEMPTS => {
+            warn!("Cannot generate a level from the seed {seed:x}, trying another one: {error}");
+            generation.spawn(new_seed());
+        }
+        Err(error) => {
+            error!(
+                "Cannot generate a level after {} attempts: {error}",
+                generation.attempts
+            );
+            generation.failed = true;
+        }
+    }
+}
+
+/// The level is only wanted while the player waits for it in the title scene
+pub(super) fn cancel_generation(mut generation: ResMut<LevelGeneration>) {
+    generation.cancel();
+}
@@ -1,12 +1,11 @@
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 use hashbrown::HashMap;
-use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use game_core::level::LevelState;
+use game_core::level::{LevelPack, LevelState};
 
-#[derive(Asset, TypePath, Serialize, Deserialize, Deref, DerefMut)]
+#[derive(Asset, TypePath, Deref, DerefMut)]
 pub struct LevelStateAsset(LevelState);
 
 impl LevelStateAsset {
@@ -15,43 +14,98 @@ impl LevelStateAsset {
     }
 }
 
+/// The pack along with the handles of its levels, which are loaded with it
+#[derive(Asset, TypePath, Deref)]
+pub struct LevelPackAsset {
+    #[deref]
+    pack: LevelPack,
+    levels: Vec<Handle<LevelStateAsset>>,
+}
+
+impl LevelPackAsset {
+    pub fn new(pack: LevelPack, levels: Vec<Handle<LevelStateAsset>>) -> LevelPackAsset {
+        LevelPackAsset { pack, levels }
+    }
+
+    pub fn handles(&self) -> &[Handle<LevelStateAsset>] {
+        &self.levels
+    }
+}
+
 #[derive(AssetCollection, Resource)]
 pub struct LevelHandles {
-    #[asset(
-        paths(
-            "levels/stock/1.lvl",
-            "levels/stock/2.lvl",
-            "levels/stock/3.lvl",
-            "levels/stock/4.lvl",
-            "levels/stock/5.lvl",
-            "levels/stock/6.lvl",
-            "levels/stock/7.lvl",
-            "levels/stock/8.lvl",
-            "levels/stock/9.lvl",
-            "levels/stock/10.lvl",
-            "levels/stock/11.lvl",
-            "levels/stock/12.lvl",
-            "levels/stock/13.lvl",
-            "levels/stock/14.lvl",
-            "levels/stock/15.lvl",
-            "levels/stock/16.lvl",
-        ),
-        collection(typed)
-    )]
+    #[asset(path = "levels/stock.lvl")]
+    stock_pack: Handle<LevelPackAsset>,
     stock: Vec<Handle<LevelStateAsset>>,
     custom: HashMap<Uuid, Handle<LevelStateAsset>>,
+    /// Packs found in the packs folder, sorted by their key
+    packs: Vec<(String, Handle<LevelPackAsset>)>,
+    pack_levels: HashMap<String, Vec<Handle<LevelStateAsset>>>,
 }
 
 impl LevelHandles {
-    pub fn get_stock(&self, index: usize) -> &Handle<LevelStateAsset> {
-        &self.stock[index]
+    pub fn get_stock_pack(&self) -> &Handle<LevelPackAsset> {
+        &self.stock_pack
+    }
+
+    pub fn get_stock(&self, index: usize) -> Option<&Handle<LevelStateAsset>> {
+        self.stock.get(index)
+    }
+
+    pub fn number_stock(&self) -> usize {
+        self.stock.len()
     }
 
     pub fn get_custom(&self, uuid: &Uuid) -> Option<&Handle<LevelStateAsset>> {
         self.custom.get(uuid)
     }
 
+    pub fn get_pack(&self, index: usize) -> Option<&(String, Handle<LevelPackAsset>)> {
+        self.packs.get(index)
+    }
+
+    pub fn get_pack_by_key(&self, key: &str) -> Option<&Handle<LevelPackAsset>> {
+        self.packs
+            .iter()
+            .find(|(pack_key, _)| pack_key == key)
+            .map(|(_, handle)| handle)
+    }
+
+    pub fn get_pack_level(&self, key: &str, index: usize) -> Option<&Handle<LevelStateAsset>> {
+        self.pack_levels
+            .get(key)
+            .and_then(|levels| levels.get(index))
+    }
+
+    pub fn number_packs(&self) -> usize {
+        self.packs.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Handle<LevelStateAsset>> {
+        self.stock
+            .iter()
+            .chain(self.custom.values())
+            .chain(self.pack_levels.values().flatten())
+    }
+
+    pub fn set_stock(&mut self, handles: Vec<Handle<LevelStateAsset>>) {
+        self.stock = handles;
+    }
+
     pub fn insert_custom(&mut self, uuid: Uuid, handle: Handle<LevelStateAsset>) {
         self.custom.insert(uuid, handle);
     }
+
+    pub fn insert_pack(
+        &mut self,
+        key: String,
+        handle: Handle<LevelPackAsset>,
+        levels: Vec<Handle<LevelStateAsset>>,
+    ) {
+        self.pack_levels.insert(key.clone(), levels);
+        self.packs.retain(|(pack_key, _)| *pack_key != key);
+        self.packs.push((key, handle));
+        self.packs
+            .sort_by(|(a_key, _), (b_key, _)| a_key.cmp(b_key));
+    }
 }
@@ -1,6 +1,11 @@
 use bevy::prelude::*;
 
-use game_core::map::{MAP_ROWS, MapPosition};
+use game_core::{
+    level::LevelState,
+    map::{Map, MapEntity, MapPosition},
+};
+
+use crate::level::LevelResource;
 
 const SPRITE_SIZE: usize = 64;
 const SPRITE_OFFSET: usize = 32;
@@ -15,12 +20,71 @@ pub fn apply_position_to_translation(position: &MapPosition, translation: &mut V
     // calculate coords with the correct sprite dimension
     // and moving the origin/pivot from the center to the top-left
     let x = ((position.x() * SPRITE_SIZE) + SPRITE_OFFSET) as f32;
-    let y = (((MAP_ROWS - position.y()) * ENTITY_SURFACE) - ENTITY_SURFACE_OFFSET) as f32;
+    let y = -(((position.y() * ENTITY_SURFACE) + ENTITY_SURFACE_OFFSET) as f32);
 
-    // take into account the camera's default position (0, 0)
-    translation.x = x - (MAP_WIDTH / 2.0);
-    translation.y = y - (MAP_HEIGHT / 2.0);
+    translation.x = x;
+    translation.y = y;
 
     // adaptation of depthness in a 2D plane
     translation.z = position.y() as f32;
 }
+
+/// Position of the tile whose surface is under the given point of the world
+pub fn translation_to_position(translation: Vec2, map: &Map) -> Option<MapPosition> {
+    if translation.x < 0.0 || translation.y > 0.0 {
+        return None;
+    }
+
+    let position = MapPosition::new(
+        (translation.x / SPRITE_SIZE as f32) as usize,
+        (-translation.y / ENTITY_SURFACE as f32) as usize,
+    );
+    map.contains(&position).then_some(position)
+}
+
+/// Boxes show their own color, zones without a box show theirs
+pub fn entity_tint(state: &LevelState, position: &MapPosition) -> Color {
+    let entity = state.get_entity(position);
+    if entity.has_box() {
+        crate::theme::tint(state.box_color(position))
+    } else if matches!(entity, MapEntity::Z) {
+        crate::theme::tint(state.zone_color(position))
+    } else {
+        crate::theme::LIGHT
+    }
+}
+
+pub fn fit_camera_to_level(
+    level: Res<LevelResource>,
+    mut camera: Query<(&mut Transform, &mut Projection), With<Camera2d>>,
+) {
+    let Ok((mut transform, mut projection)) = camera.single_mut() else {
+        return;
+    };
+
+    let map = level.state().map();
+    let surface_height = (map.height() * ENTITY_SURFACE) as f32;
+    let width = (map.width() * SPRITE_SIZE) as f32;
+    let height = surface_height + (SPRITE_SIZE - ENTITY_SURFACE) as f32;
+
+    // center the map, maps bigger than the default one are zoomed out to fit
+    transform.translation.x = width / 2.0;
+    transform.translation.y = height / 2.0 - surface_height;
+
+    if let Projection::Orthographic(orthographic) = projection.as_mut() {
+        orthographic.scale = (width / MAP_WIDTH).max(height / MAP_HEIGHT).max(1.0);
+    }
+}
+
+pub fn reset_camera(mut camera: Query<(&mut Transform, &mut Projection), With<Camera2d>>) {
+    let Ok((mut transform, mut projection)) = camera.single_mut() else {
+        return;
+    };
+
+    transform.translation.x = 0.0;
+    transform.translation.y = 0.0;
+
+    if let Projection::Orthographic(orthographic) = projection.as_mut() {
+        orthographic.scale = 1.0;
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use game_core::level::LevelKind;
+use uuid::Uuid;
 
 #[derive(Event)]
 pub struct LevelInsertionEvent(LevelKind);
@@ -9,7 +10,16 @@ impl LevelInsertionEvent {
         LevelInsertionEvent(kind)
     }
 
+    /// Every call asks for a level never seen before
+    pub fn generated() -> LevelInsertionEvent {
+        LevelInsertionEvent(LevelKind::Generated(new_seed()))
+    }
+
     pub fn kind(&self) -> &LevelKind {
         &self.0
     }
 }
+
+pub(super) fn new_seed() -> u64 {
+    Uuid::new_v4().as_u64_pair().0
+}
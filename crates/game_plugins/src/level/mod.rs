@@ -1,17 +1,27 @@
 mod brush;
+mod difficulty;
 mod done_timer;
 mod entity;
+mod generation;
 mod handles;
 mod helpers;
 mod insertion;
+mod pack_loader;
 mod plugin;
 mod resource;
+mod xsb_loader;
 
-// TODO: Move level validity into editor mod or similar
-pub use brush::{Brush, BrushEntity, BrushSprite, LevelValidity};
+pub use brush::{Brush, BrushEntity, BrushSprite};
+pub use difficulty::LevelDifficulties;
 pub use entity::EntityComponent;
-pub use handles::{LevelHandles, LevelStateAsset};
-pub use helpers::apply_position_to_translation;
+pub use generation::{GenerationStatus, LevelGeneration};
+pub use handles::{LevelHandles, LevelPackAsset, LevelStateAsset};
+pub use helpers::{
+    apply_position_to_translation, entity_tint, fit_camera_to_level, reset_camera,
+    translation_to_position,
+};
 pub use insertion::LevelInsertionEvent;
+pub use pack_loader::{LevelPackFile, LevelPackLoader, LevelPackLoaderError};
 pub use plugin::Plugin;
-pub use resource::{LevelResource, TOTAL_CUSTOM_LEVELS, TOTAL_STOCK_LEVELS};
+pub use resource::LevelResource;
+pub use xsb_loader::{XsbLoader, XsbLoaderError};
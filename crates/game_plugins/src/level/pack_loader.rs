@@ -0,0 +1,86 @@
+use std::{fmt, io};
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use game_core::level::LevelPack;
+use ron::error::SpannedError;
+use serde::{Deserialize, Serialize};
+
+use crate::level::{LevelPackAsset, LevelStateAsset};
+
+/// Layout of the level files, older files with a single level are read as a pack of one
+#[derive(Serialize, Deserialize)]
+pub struct LevelPackFile(#[serde(with = "game_core::versioned")] pub LevelPack);
+
+#[derive(Debug)]
+pub enum LevelPackLoaderError {
+    Io(io::Error),
+    Ron(SpannedError),
+}
+
+impl fmt::Display for LevelPackLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelPackLoaderError::Io(error) => write!(f, "could not read the level file: {error}"),
+            LevelPackLoaderError::Ron(error) => {
+                write!(f, "could not parse the level file: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LevelPackLoaderError {}
+
+impl From<io::Error> for LevelPackLoaderError {
+    fn from(error: io::Error) -> Self {
+        LevelPackLoaderError::Io(error)
+    }
+}
+
+impl From<SpannedError> for LevelPackLoaderError {
+    fn from(error: SpannedError) -> Self {
+        LevelPackLoaderError::Ron(error)
+    }
+}
+
+/// Loads a level pack, every level of the pack is also available through
+/// its number, as in `stock.lvl#3`
+#[derive(Default)]
+pub struct LevelPackLoader;
+
+impl AssetLoader for LevelPackLoader {
+    type Asset = LevelPackAsset;
+    type Settings = ();
+    type Error = LevelPackLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let LevelPackFile(pack) = ron::de::from_bytes(&bytes)?;
+
+        let levels = pack
+            .levels()
+            .iter()
+            .enumerate()
+            .map(|(index, level)| {
+                load_context.add_labeled_asset(
+                    (index + 1).to_string(),
+                    LevelStateAsset::new(level.state().clone()),
+                )
+            })
+            .collect();
+
+        Ok(LevelPackAsset::new(pack, levels))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["lvl"]
+    }
+}
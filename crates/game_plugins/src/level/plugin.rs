@@ -1,19 +1,123 @@
-use bevy::{app::Plugin as BevyPlugin, prelude::*};
-use bevy_common_assets::ron::RonAssetPlugin;
-use game_core::level::LevelKind;
+#[cfg(not(target_family = "wasm"))]
+use std::{env, path::PathBuf};
+
+use bevy::{app::Plugin as BevyPlugin, asset::LoadedFolder, prelude::*};
+use game_core::level::{LevelKind, LevelState, PlayMode};
 use uuid::Uuid;
 
-use crate::level::handles::{LevelHandles, LevelStateAsset};
+use crate::level::difficulty::{LevelDifficulties, estimate_difficulties, poll_difficulties};
+use crate::level::generation::{LevelGeneration, cancel_generation, poll_generation};
+use crate::level::handles::{LevelHandles, LevelPackAsset, LevelStateAsset};
 use crate::level::insertion::LevelInsertionEvent;
+use crate::level::pack_loader::LevelPackLoader;
 use crate::level::resource::LevelResource;
-use crate::state::GameStateTransitionEvent;
+use crate::level::xsb_loader::XsbLoader;
+use crate::save_file::SaveFile;
+use crate::state::{GameState, GameStateTransitionEvent};
+
+/// Folder where extra level packs can be dropped, each file is a pack
+#[cfg(not(target_family = "wasm"))]
+const PACKS_FOLDER: &str = "levels/packs";
 
 pub struct Plugin;
 
+#[derive(Resource)]
+struct PacksFolder(Handle<LoadedFolder>);
+
 impl BevyPlugin for Plugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((RonAssetPlugin::<LevelStateAsset>::new(&["lvl"]),))
-            .add_systems(Update, insert_level.run_if(on_event::<LevelInsertionEvent>));
+        app.init_asset::<LevelStateAsset>()
+            .init_asset::<LevelPackAsset>()
+            .init_asset_loader::<LevelPackLoader>()
+            .init_asset_loader::<XsbLoader>()
+            .init_resource::<LevelDifficulties>()
+            .init_resource::<LevelGeneration>()
+            .add_systems(OnExit(GameState::Loading), insert_stock_level_handles)
+            .add_systems(
+                Update,
+                (
+                    insert_level.run_if(on_event::<LevelInsertionEvent>),
+                    poll_generation.run_if(in_state(GameState::Title)),
+                ),
+            )
+            .add_systems(OnExit(GameState::Title), cancel_generation)
+            .add_systems(
+                Update,
+                insert_pack_handles
+                    .run_if(resource_exists::<PacksFolder>)
+                    .run_if(on_event::<AssetEvent<LoadedFolder>>),
+            )
+            .add_systems(
+                Update,
+                (estimate_difficulties, poll_difficulties).run_if(resource_exists::<LevelHandles>),
+            );
+
+        // packs can only be found in the file system of desktop builds
+        #[cfg(not(target_family = "wasm"))]
+        app.add_systems(OnExit(GameState::Loading), load_packs_folder);
+    }
+}
+
+fn insert_stock_level_handles(
+    mut level_handles: ResMut<LevelHandles>,
+    level_packs_assets: Res<Assets<LevelPackAsset>>,
+) {
+    let handles = level_packs_assets
+        .get(level_handles.get_stock_pack())
+        .expect("The stock levels were not loaded")
+        .handles()
+        .to_vec();
+    assert!(!handles.is_empty(), "The stock pack has no levels");
+    level_handles.set_stock(handles);
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn load_packs_folder(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let assets_path = if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
+        PathBuf::from(manifest_dir).join("assets")
+    } else {
+        PathBuf::from("assets")
+    };
+
+    if assets_path.join(PACKS_FOLDER).is_dir() {
+        commands.insert_resource(PacksFolder(asset_server.load_folder(PACKS_FOLDER)));
+    }
+}
+
+fn insert_pack_handles(
+    mut asset_event_reader: EventReader<AssetEvent<LoadedFolder>>,
+    mut level_handles: ResMut<LevelHandles>,
+    packs_folder: Res<PacksFolder>,
+    loaded_folders: Res<Assets<LoadedFolder>>,
+    level_packs_assets: Res<Assets<LevelPackAsset>>,
+) {
+    for event in asset_event_reader.read() {
+        if !event.is_loaded_with_dependencies(&packs_folder.0) {
+            continue;
+        }
+        let Some(folder) = loaded_folders.get(&packs_folder.0) else {
+            continue;
+        };
+
+        for handle in &folder.handles {
+            let Ok(handle) = handle.clone().try_typed::<LevelPackAsset>() else {
+                continue;
+            };
+            let key = handle
+                .path()
+                .and_then(|path| path.path().file_stem())
+                .map(|stem| stem.to_string_lossy().into_owned());
+            let (Some(key), Some(pack)) = (key, level_packs_assets.get(&handle)) else {
+                continue;
+            };
+            if pack.is_empty() {
+                warn!("Skipping the level pack {key}, it has no levels");
+                continue;
+            }
+
+            let levels = pack.handles().to_vec();
+            level_handles.insert_pack(key, handle, levels);
+        }
     }
 }
 
@@ -23,14 +127,25 @@ fn insert_level(
     mut scene_transition_event_writer: EventWriter<GameStateTransitionEvent>,
     level_handles: Res<LevelHandles>,
     level_states_assets: Res<Assets<LevelStateAsset>>,
+    level_packs_assets: Res<Assets<LevelPackAsset>>,
+    save_file: Res<SaveFile>,
+    mut generation: ResMut<LevelGeneration>,
 ) {
     if let Some(level_insertion_event) = level_insertion_event_reader.read().next() {
         match level_insertion_event.kind() {
             LevelKind::Stock(index) => {
-                let state = **level_states_assets
-                    .get(level_handles.get_stock(*index))
-                    .unwrap();
-                let level = LevelResource::new(level_insertion_event.kind().clone(), state);
+                let pack = level_packs_assets
+                    .get(level_handles.get_stock_pack())
+                    .expect("Cannot get the stock levels");
+                let pack_level = pack.level(*index).expect("Cannot get the stock level");
+                let level = LevelResource::new(
+                    level_insertion_event.kind().clone(),
+                    pack_level.state().clone(),
+                    pack.level_undo_policy(*index)
+                        .unwrap_or(save_file.get_undo_policy()),
+                    save_file.get_play_mode(),
+                )
+                .with_pack_level(pack_level);
 
                 commands.insert_resource(level);
                 scene_transition_event_writer.write(GameStateTransitionEvent::level());
@@ -38,20 +153,62 @@ fn insert_level(
             LevelKind::Custom(payload) => {
                 let parsed_payload: Vec<&str> = payload.split('$').collect();
                 let uuid = Uuid::parse_str(parsed_payload[1]).expect("Cannot parse uuid");
-                let state = **level_states_assets
-                    .get(level_handles.get_custom(&uuid).unwrap())
-                    .unwrap();
-                let level = LevelResource::new(level_insertion_event.kind().clone(), state);
+                // the file may be missing, or written by a newer version of the game
+                let Some(state) = level_handles
+                    .get_custom(&uuid)
+                    .and_then(|handle| level_states_assets.get(handle))
+                else {
+                    error!("Cannot open the custom level {}", parsed_payload[0]);
+                    return;
+                };
+                let state = LevelState::clone(state);
+                let level = LevelResource::new(
+                    level_insertion_event.kind().clone(),
+                    state,
+                    save_file.get_undo_policy(),
+                    save_file.get_play_mode(),
+                );
+
+                commands.insert_resource(level);
+                scene_transition_event_writer.write(GameStateTransitionEvent::level());
+            }
+            LevelKind::Pack(key, index) => {
+                let Some(pack) = level_handles
+                    .get_pack_by_key(key)
+                    .and_then(|handle| level_packs_assets.get(handle))
+                else {
+                    error!("Cannot open the pack {key}");
+                    return;
+                };
+                let Some(pack_level) = pack.level(*index) else {
+                    error!("Cannot open the level {} of the pack {key}", index + 1);
+                    return;
+                };
+                let level = LevelResource::new(
+                    level_insertion_event.kind().clone(),
+                    pack_level.state().clone(),
+                    pack.level_undo_policy(*index)
+                        .unwrap_or(save_file.get_undo_policy()),
+                    save_file.get_play_mode(),
+                )
+                .with_pack_level(pack_level);
 
                 commands.insert_resource(level);
                 scene_transition_event_writer.write(GameStateTransitionEvent::level());
             }
             LevelKind::Editable(state) => {
-                let level = LevelResource::new(level_insertion_event.kind().clone(), *state);
+                let level = LevelResource::new(
+                    level_insertion_event.kind().clone(),
+                    state.clone(),
+                    save_file.get_undo_policy(),
+                    PlayMode::default(),
+                );
 
                 commands.insert_resource(level);
                 scene_transition_event_writer.write(GameStateTransitionEvent::level());
             }
+            // the level scene starts once the level is ready, see `poll_generation`
+            LevelKind::Generated(seed) => generation.start(*seed),
         }
     }
 }
@@ -5,7 +5,7 @@ use std::{
 
 use bevy::prelude::*;
 use game_core::{
-    level::{Level, LevelKind, LevelState},
+    level::{Level, LevelKind, LevelState, PackLevel, PlayMode, UndoPolicy},
     map::MapEntity,
 };
 
@@ -15,13 +15,11 @@ use crate::{
     level::{EntityComponent, done_timer::LevelDoneTimer},
 };
 
-pub const TOTAL_STOCK_LEVELS: usize = 16;
-pub const TOTAL_CUSTOM_LEVELS: usize = 16;
-
 #[derive(Resource, Default)]
 pub struct LevelResource {
     inner: Level,
     done_timer: LevelDoneTimer,
+    pack_level: Option<PackLevel>,
 }
 
 impl Deref for LevelResource {
@@ -39,13 +37,30 @@ impl DerefMut for LevelResource {
 }
 
 impl LevelResource {
-    pub fn new(kind: LevelKind, state: LevelState) -> LevelResource {
+    pub fn new(
+        kind: LevelKind,
+        state: LevelState,
+        undo_policy: UndoPolicy,
+        mode: PlayMode,
+    ) -> LevelResource {
         LevelResource {
-            inner: Level::new(kind, state),
+            inner: Level::new(kind, state)
+                .with_undo_policy(undo_policy)
+                .with_mode(mode),
             ..LevelResource::default()
         }
     }
 
+    /// Keeps the name, par and hint of the level when it comes from a pack
+    pub fn with_pack_level(mut self, pack_level: &PackLevel) -> Self {
+        self.pack_level = Some(pack_level.clone());
+        self
+    }
+
+    pub fn pack_level(&self) -> Option<&PackLevel> {
+        self.pack_level.as_ref()
+    }
+
     pub fn spawn(&mut self, commands: &mut Commands, images: &Images) {
         self.reset_done_timer();
 
@@ -62,6 +77,12 @@ impl LevelResource {
             images.character.clone(),
         );
 
+        self.spawn_map(commands, images);
+    }
+
+    /// Spawns the entities of the map, without the character
+    pub fn spawn_map(&self, commands: &mut Commands, images: &Images) {
+        let state = self.inner.state();
         self.inner
             .loop_over_entity_and_position(|entity, position| {
                 let texture = match entity {
@@ -70,6 +91,17 @@ impl LevelResource {
                     MapEntity::Z => images.entity_zone.clone(),
                     MapEntity::B => images.entity_box.clone(),
                     MapEntity::P => images.entity_placed_box.clone(),
+                    MapEntity::I => images.entity_ice.clone(),
+                    MapEntity::J => images.entity_box_on_ice.clone(),
+                    MapEntity::T => images.entity_teleporter.clone(),
+                    MapEntity::U => images.entity_box_on_teleporter.clone(),
+                    MapEntity::S => images.entity_plate.clone(),
+                    MapEntity::Q => images.entity_box_on_plate.clone(),
+                    MapEntity::D if state.is_door_open(&position) => {
+                        images.entity_open_door.clone()
+                    }
+                    MapEntity::D => images.entity_door.clone(),
+                    MapEntity::H => images.entity_hole.clone(),
                 };
                 EntityComponent::spawn(position, commands, texture);
             });
@@ -91,9 +123,10 @@ impl LevelResource {
         self.done_timer.just_finished()
     }
 
-    pub fn is_last(&self) -> bool {
+    /// Whether this is the last level of the stock levels or its pack, given how many there are
+    pub fn is_last(&self, total_levels: usize) -> bool {
         match self.inner.kind() {
-            LevelKind::Stock(index) => *index == TOTAL_STOCK_LEVELS - 1,
+            LevelKind::Stock(index) | LevelKind::Pack(_, index) => *index + 1 >= total_levels,
             _ => false,
         }
     }
@@ -0,0 +1,103 @@
+use std::{fmt, io, string::FromUtf8Error};
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use game_core::level::{LevelPack, LevelState, PackLevel, XsbError};
+
+use crate::level::{LevelPackAsset, LevelStateAsset};
+
+#[derive(Debug)]
+pub enum XsbLoaderError {
+    Io(io::Error),
+    Utf8(FromUtf8Error),
+    Xsb(XsbError),
+}
+
+impl fmt::Display for XsbLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XsbLoaderError::Io(error) => write!(f, "could not read the level file: {error}"),
+            XsbLoaderError::Utf8(error) => write!(f, "the level file is not valid text: {error}"),
+            XsbLoaderError::Xsb(error) => write!(f, "could not parse the level file: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for XsbLoaderError {}
+
+impl From<io::Error> for XsbLoaderError {
+    fn from(error: io::Error) -> Self {
+        XsbLoaderError::Io(error)
+    }
+}
+
+impl From<FromUtf8Error> for XsbLoaderError {
+    fn from(error: FromUtf8Error) -> Self {
+        XsbLoaderError::Utf8(error)
+    }
+}
+
+/// Loads the levels of a XSB file as a pack titled after the file, every level of the
+/// file is also available through its number in the pack, as in `pack.sok#3`
+#[derive(Default)]
+pub struct XsbLoader;
+
+impl AssetLoader for XsbLoader {
+    type Asset = LevelPackAsset;
+    type Settings = ();
+    type Error = XsbLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text = String::from_utf8(bytes)?;
+
+        let title = load_context
+            .path()
+            .file_stem()
+            .map_or(String::new(), |stem| stem.to_string_lossy().into_owned());
+        let mut pack = LevelPack::new(&title);
+        let mut levels = vec![];
+        let mut first_error = None;
+
+        for (index, level) in LevelState::from_xsb_collection(&text)
+            .into_iter()
+            .enumerate()
+        {
+            match level {
+                Ok(state) => {
+                    levels.push(load_context.add_labeled_asset(
+                        (levels.len() + 1).to_string(),
+                        LevelStateAsset::new(state.clone()),
+                    ));
+                    pack = pack.with_level(PackLevel::new(&format!("Level {}", index + 1), state));
+                }
+                Err(error) => {
+                    warn!(
+                        "Skipping level {} of {}: {error}",
+                        index + 1,
+                        load_context.path().display()
+                    );
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+
+        if pack.is_empty() {
+            return Err(XsbLoaderError::Xsb(first_error.unwrap_or(XsbError::Empty)));
+        }
+
+        Ok(LevelPackAsset::new(pack, levels))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["xsb", "sok"]
+    }
+}
@@ -12,6 +12,7 @@
 #![cfg_attr(target_family = "wasm", allow(dead_code))]
 
 mod character;
+mod clipboard;
 mod input;
 mod level;
 mod save_file;
@@ -0,0 +1,175 @@
+use std::{
+    env,
+    fs::{copy, create_dir_all, read_dir},
+    io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use bevy::{asset::io::AssetSourceBuilder, prelude::*};
+
+/// Asset source of the user data, as in `data://game.dat`
+pub const DATA_SOURCE: &str = "data";
+
+const DATA_DIR_FLAG: &str = "--data-dir";
+const DATA_DIR_VAR: &str = "PUSHIN_BOXES_DATA_DIR";
+const APP_NAME: &str = "pushin_boxes";
+
+const SAVE_FILE: &str = "game.dat";
+const CUSTOM_LEVELS_FOLDER: &str = "levels/custom";
+
+/// Folder of the save file and the custom levels, chosen with the `--data-dir` flag,
+/// the `PUSHIN_BOXES_DATA_DIR` variable, or else the data folder of the platform
+pub fn data_dir() -> &'static Path {
+    static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+    DATA_DIR.get_or_init(|| {
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            if arg == DATA_DIR_FLAG
+                && let Some(path) = args.next()
+            {
+                return PathBuf::from(path);
+            }
+            if let Some(path) = arg.strip_prefix(&format!("{DATA_DIR_FLAG}=")) {
+                return PathBuf::from(path);
+            }
+        }
+
+        if let Some(path) = env::var_os(DATA_DIR_VAR).filter(|path| !path.is_empty()) {
+            return PathBuf::from(path);
+        }
+
+        platform_data_dir().map_or_else(legacy_dir, |path| path.join(APP_NAME))
+    })
+}
+
+pub fn save_file_path() -> PathBuf {
+    data_dir().join(SAVE_FILE)
+}
+
+pub fn custom_level_path(uuid: &str) -> PathBuf {
+    data_dir()
+        .join(CUSTOM_LEVELS_FOLDER)
+        .join(format!("{uuid}.lvl"))
+}
+
+/// Path of a custom level for the asset server
+pub fn custom_level_asset_path(uuid: &str) -> String {
+    format!("{DATA_SOURCE}://{CUSTOM_LEVELS_FOLDER}/{uuid}.lvl#1")
+}
+
+pub fn save_file_asset_path() -> String {
+    format!("{DATA_SOURCE}://{SAVE_FILE}")
+}
+
+/// Has to be called before the asset plugin is added. Browsers have no data folder,
+/// so there the source is the assets folder, where nothing is ever saved
+pub fn register_data_source(app: &mut App) {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        // the folder has to exist to be watched, failing here is reported by the migration
+        let _ = create_dir_all(data_dir());
+
+        let path = data_dir().to_string_lossy().into_owned();
+        app.register_asset_source(
+            DATA_SOURCE,
+            AssetSourceBuilder::platform_default(&path, None),
+        );
+    }
+
+    #[cfg(target_family = "wasm")]
+    {
+        app.register_asset_source(
+            DATA_SOURCE,
+            AssetSourceBuilder::platform_default("assets", None),
+        );
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn platform_data_dir() -> Option<PathBuf> {
+    let home = || {
+        env::var_os("HOME")
+            .filter(|home| !home.is_empty())
+            .map(PathBuf::from)
+    };
+
+    if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| home().map(|home| home.join(".local").join("share")))
+    }
+}
+
+#[cfg(target_family = "wasm")]
+fn platform_data_dir() -> Option<PathBuf> {
+    None
+}
+
+/// Assets folder where the data was kept before it had its own folder
+fn legacy_dir() -> PathBuf {
+    if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
+        PathBuf::from(manifest_dir).join("assets")
+    } else {
+        PathBuf::from("assets")
+    }
+}
+
+/// Copies the save file and the custom levels from the assets folder, the first time
+/// the game runs with a data folder. The old files are left in place
+pub fn migrate_legacy_data() {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        let data_dir = data_dir();
+        let legacy_dir = legacy_dir();
+
+        if data_dir.join(SAVE_FILE).exists() || !legacy_dir.join(SAVE_FILE).exists() {
+            if let Err(error) = create_dir_all(data_dir) {
+                error!(
+                    "Cannot create the data folder {}: {error}",
+                    data_dir.display()
+                );
+            }
+            return;
+        }
+
+        match copy_legacy_data(&legacy_dir, data_dir) {
+            Ok(()) => info!(
+                "Copied the save data from {} to {}",
+                legacy_dir.display(),
+                data_dir.display()
+            ),
+            Err(error) => error!(
+                "Cannot copy the save data to {}: {error}",
+                data_dir.display()
+            ),
+        }
+    }
+}
+
+fn copy_legacy_data(legacy_dir: &Path, data_dir: &Path) -> io::Result<()> {
+    let custom_levels = data_dir.join(CUSTOM_LEVELS_FOLDER);
+    create_dir_all(&custom_levels)?;
+
+    let legacy_custom_levels = legacy_dir.join(CUSTOM_LEVELS_FOLDER);
+    if legacy_custom_levels.is_dir() {
+        for entry in read_dir(legacy_custom_levels)? {
+            let path = entry?.path();
+            if let Some(name) = path.file_name()
+                && path.extension().is_some_and(|extension| extension == "lvl")
+            {
+                copy(&path, custom_levels.join(name))?;
+            }
+        }
+    }
+
+    // last, so an interrupted copy is tried again on the next launch
+    copy(legacy_dir.join(SAVE_FILE), data_dir.join(SAVE_FILE))?;
+    Ok(())
+}
@@ -1,16 +1,22 @@
 use bevy::{asset::LoadState, prelude::*};
+use serde::{Deserialize, Serialize};
 
-use crate::save_file::SaveFile;
+use crate::save_file::{SaveFile, data_dir::save_file_asset_path};
+
+/// The save file as written to the disk, along with the version of its format
+#[derive(Asset, TypePath, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SaveFileAsset(#[serde(with = "game_core::versioned")] pub SaveFile);
 
 #[derive(Asset, TypePath, Clone, Resource)]
 pub struct SaveFileHandle {
-    pub value: Handle<SaveFile>,
+    pub value: Handle<SaveFileAsset>,
 }
 
 impl SaveFileHandle {
     pub fn load(mut commands: Commands, asset_server: Res<AssetServer>) {
         commands.insert_resource(SaveFileHandle {
-            value: asset_server.load("game.dat"),
+            value: asset_server.load(save_file_asset_path()),
         });
     }
 
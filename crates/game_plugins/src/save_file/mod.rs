@@ -1,6 +1,8 @@
+mod data_dir;
 mod handle;
 mod plugin;
 mod save_file;
 
+pub use data_dir::{custom_level_asset_path, custom_level_path, register_data_source};
 pub use plugin::Plugin;
 pub use save_file::SaveFile;
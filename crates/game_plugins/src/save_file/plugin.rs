@@ -5,7 +5,11 @@ use uuid::Uuid;
 
 use crate::{
     level::LevelHandles,
-    save_file::{SaveFile, handle::SaveFileHandle},
+    save_file::{
+        SaveFile,
+        data_dir::{custom_level_asset_path, migrate_legacy_data},
+        handle::{SaveFileAsset, SaveFileHandle},
+    },
     state::GameState,
 };
 
@@ -13,8 +17,11 @@ pub struct Plugin;
 
 impl BevyPlugin for Plugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((RonAssetPlugin::<SaveFile>::new(&["dat"]),))
-            .add_systems(OnEnter(GameState::Loading), SaveFileHandle::load)
+        app.add_plugins((RonAssetPlugin::<SaveFileAsset>::new(&["dat"]),))
+            .add_systems(
+                OnEnter(GameState::Loading),
+                (migrate_legacy_data, SaveFileHandle::load).chain(),
+            )
             .add_systems(
                 Update,
                 SaveFile::insert
@@ -36,7 +43,9 @@ fn insert_custom_level_handles(
     for (_, (key, _)) in save_file.ordered_custom_records() {
         let split_key: Vec<&str> = key.split('$').collect();
         let uuid = Uuid::parse_str(split_key[1]).expect("Cannot parse uuid");
-        let path = format!("levels/custom/{}.lvl", &split_key[1]);
-        level_handles.insert_custom(uuid, asset_server.load(path));
+        level_handles.insert_custom(
+            uuid,
+            asset_server.load(custom_level_asset_path(split_key[1])),
+        );
     }
 }
@@ -1,29 +1,81 @@
-use std::{env, fs::File, io::Write, iter::Enumerate, path::PathBuf, slice::Iter, vec::IntoIter};
+use std::{fs::write, iter::Enumerate, slice::Iter, vec::IntoIter};
 
-use bevy::{asset::LoadState, prelude::*, reflect::TypePath};
+use bevy::{
+    asset::{AssetLoadError, LoadState, io::AssetReaderError},
+    prelude::*,
+};
 use hashbrown::HashMap;
 use ron::ser as serialize_ron;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
-use game_core::level::{Level, LevelKind, LevelRecord};
+use game_core::{
+    level::{Level, LevelKind, LevelRecord, PlayMode, RecordRanking, UndoPolicy},
+    versioned::Migrate,
+};
 
-use crate::{level::TOTAL_STOCK_LEVELS, save_file::handle::SaveFileHandle};
+use crate::save_file::{
+    data_dir::save_file_path,
+    handle::{SaveFileAsset, SaveFileHandle},
+};
 
 pub const INITIAL_VOLUME: f64 = 0.5;
 
-#[derive(Asset, TypePath, Serialize, Deserialize, Clone, Resource)]
+#[derive(Serialize, Deserialize, Clone, Resource)]
 pub struct SaveFile {
     volume: f64,
+    #[serde(default)]
+    undo_policy: UndoPolicy,
+    #[serde(default)]
+    record_ranking: RecordRanking,
+    #[serde(default)]
+    play_mode: PlayMode,
     stock_records: Vec<LevelRecord>,
     custom_records: HashMap<String, LevelRecord>,
+    /// Records of the levels played backwards, which never unlock new levels
+    #[serde(default)]
+    pull_stock_records: HashMap<usize, LevelRecord>,
+    #[serde(default)]
+    pull_custom_records: HashMap<String, LevelRecord>,
+    /// Records of the levels in the packs, by the key of the pack and the index of the level
+    #[serde(default)]
+    pack_records: HashMap<String, HashMap<usize, LevelRecord>>,
+    #[serde(default)]
+    pull_pack_records: HashMap<String, HashMap<usize, LevelRecord>>,
+    /// Why the file on the disk could not be read or written, it is never written
+    /// again while set
+    #[serde(skip)]
+    error: Option<String>,
 }
 
 impl Default for SaveFile {
     fn default() -> SaveFile {
         SaveFile {
             volume: INITIAL_VOLUME,
+            undo_policy: UndoPolicy::default(),
+            record_ranking: RecordRanking::default(),
+            play_mode: PlayMode::default(),
             stock_records: vec![LevelRecord::default()],
             custom_records: HashMap::default(),
+            pull_stock_records: HashMap::default(),
+            pull_custom_records: HashMap::default(),
+            pack_records: HashMap::default(),
+            pull_pack_records: HashMap::default(),
+            error: None,
+        }
+    }
+}
+
+impl Migrate for SaveFile {
+    const VERSION: u32 = 1;
+
+    /// Files from before the versions only lack the fields that have defaults
+    fn migrate<'de, D: Deserializer<'de>>(
+        version: u32,
+        deserializer: D,
+    ) -> Result<SaveFile, D::Error> {
+        match version {
+            0 | 1 => SaveFile::deserialize(deserializer),
+            _ => unreachable!("Newer versions are rejected before the migration"),
         }
     }
 }
@@ -33,62 +85,119 @@ impl SaveFile {
         mut commands: Commands,
         asset_server: Res<AssetServer>,
         save_file_handle: Res<SaveFileHandle>,
-        save_file: Res<Assets<SaveFile>>,
+        save_file: Res<Assets<SaveFileAsset>>,
     ) {
         let load_state = asset_server.get_load_state(save_file_handle.value.id());
 
-        let file = if matches!(load_state, Some(LoadState::Loaded)) {
-            save_file.get(&save_file_handle.value).unwrap().clone()
-        } else {
-            SaveFile::default()
+        let file = match load_state {
+            Some(LoadState::Loaded) => save_file.get(&save_file_handle.value).unwrap().0.clone(),
+            // there is no file until the first save
+            Some(LoadState::Failed(error))
+                if !matches!(
+                    *error,
+                    AssetLoadError::AssetReaderError(AssetReaderError::NotFound(_))
+                ) =>
+            {
+                error!("Cannot load the save file, the progress will not be saved: {error}");
+                SaveFile {
+                    error: Some(format!("Cannot load the save file: {error}")),
+                    ..SaveFile::default()
+                }
+            }
+            _ => SaveFile::default(),
         };
 
         commands.insert_resource(file);
     }
 
-    pub fn save(&self) {
+    /// A file that cannot be written is not tried again, the progress is kept until
+    /// the game is closed
+    pub fn save(&mut self) {
+        if self.error.is_some() {
+            return;
+        }
+
         #[cfg(not(target_family = "wasm"))]
         {
-            let path = if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
-                PathBuf::from(manifest_dir).join("assets").join("game.dat")
-            } else {
-                PathBuf::from("./assets").join("game.dat")
-            };
-
-            if let Ok(serialized_string) = serialize_ron::to_string(self) {
-                let mut file = File::create(path).unwrap();
-                file.write_all(serialized_string.as_bytes()).unwrap();
+            if let Ok(serialized_string) = serialize_ron::to_string(&SaveFileAsset(self.clone()))
+                && let Err(error) = write(save_file_path(), serialized_string)
+            {
+                error!("Cannot write the save file, the progress will not be saved: {error}");
+                self.error = Some(format!("Cannot write the save file: {error}"));
             }
         }
     }
 
-    pub fn get_record(&self, kind: &LevelKind) -> LevelRecord {
-        match kind {
-            LevelKind::Stock(index) => self.stock_records[*index].clone(),
-            LevelKind::Custom(payload) => self
+    pub fn get_record(&self, kind: &LevelKind, mode: PlayMode) -> LevelRecord {
+        match (kind, mode) {
+            (LevelKind::Stock(index), PlayMode::Push) => self.stock_records[*index].clone(),
+            (LevelKind::Custom(payload), PlayMode::Push) => self
                 .custom_records
                 .get(payload)
                 .expect("Cannot get custom record")
                 .clone(),
-            LevelKind::Editable(_) => LevelRecord::default(),
+            (LevelKind::Stock(index), PlayMode::Pull) => self
+                .pull_stock_records
+                .get(index)
+                .cloned()
+                .unwrap_or_default(),
+            (LevelKind::Custom(payload), PlayMode::Pull) => self
+                .pull_custom_records
+                .get(payload)
+                .cloned()
+                .unwrap_or_default(),
+            (LevelKind::Pack(key, index), PlayMode::Push) => self
+                .pack_records
+                .get(key)
+                .and_then(|records| records.get(index))
+                .cloned()
+                .unwrap_or_default(),
+            (LevelKind::Pack(key, index), PlayMode::Pull) => self
+                .pull_pack_records
+                .get(key)
+                .and_then(|records| records.get(index))
+                .cloned()
+                .unwrap_or_default(),
+            (LevelKind::Editable(_) | LevelKind::Generated(_), _) => LevelRecord::default(),
         }
     }
 
     pub fn set_new_record(&mut self, level: &Level) {
         let new_record = level.record();
-        let current_record = self.get_record(level.kind());
-        if new_record.is_better_than(&current_record) {
-            match level.kind() {
-                LevelKind::Stock(index) => {
+        let current_record = self.get_record(level.kind(), level.mode());
+        if new_record.is_better_than(&current_record, self.record_ranking) {
+            match (level.kind(), level.mode()) {
+                (LevelKind::Stock(index), PlayMode::Push) => {
                     self.stock_records[*index] = new_record.clone();
                 }
-                LevelKind::Custom(payload) => {
+                (LevelKind::Custom(payload), PlayMode::Push) => {
                     self.custom_records
                         .insert(payload.clone(), new_record.clone());
                 }
-                LevelKind::Editable(_) => {
+                (LevelKind::Stock(index), PlayMode::Pull) => {
+                    self.pull_stock_records.insert(*index, new_record.clone());
+                }
+                (LevelKind::Custom(payload), PlayMode::Pull) => {
+                    self.pull_custom_records
+                        .insert(payload.clone(), new_record.clone());
+                }
+                (LevelKind::Pack(key, index), PlayMode::Push) => {
+                    self.pack_records
+                        .entry(key.clone())
+                        .or_default()
+                        .insert(*index, new_record.clone());
+                }
+                (LevelKind::Pack(key, index), PlayMode::Pull) => {
+                    self.pull_pack_records
+                        .entry(key.clone())
+                        .or_default()
+                        .insert(*index, new_record.clone());
+                }
+                (LevelKind::Editable(_), _) => {
                     unreachable!("Cannot set a record for an editable level")
                 }
+                // there is no end to the generated levels, so they keep no records
+                (LevelKind::Generated(_), _) => {}
             }
         }
     }
@@ -99,12 +208,15 @@ impl SaveFile {
 
     pub fn delete_custom_level_record(&mut self, key: &str) {
         self.custom_records.remove(key);
+        self.pull_custom_records.remove(key);
     }
 
-    pub fn unlock_new_level(&mut self, level: &Level) {
-        if let LevelKind::Stock(index) = level.kind() {
+    pub fn unlock_new_level(&mut self, level: &Level, total_stock_levels: usize) {
+        if let LevelKind::Stock(index) = level.kind()
+            && !level.mode().is_pull()
+        {
             let unlocked_levels = self.unlocked_levels();
-            if unlocked_levels == index + 1 && unlocked_levels < TOTAL_STOCK_LEVELS {
+            if unlocked_levels == index + 1 && unlocked_levels < total_stock_levels {
                 self.stock_records.push(LevelRecord::default());
             }
         }
@@ -118,6 +230,35 @@ impl SaveFile {
         self.volume
     }
 
+    pub fn set_undo_policy(&mut self, undo_policy: UndoPolicy) {
+        self.undo_policy = undo_policy;
+    }
+
+    /// Set when the progress is not being saved
+    pub fn get_error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn get_undo_policy(&self) -> UndoPolicy {
+        self.undo_policy
+    }
+
+    pub fn set_record_ranking(&mut self, record_ranking: RecordRanking) {
+        self.record_ranking = record_ranking;
+    }
+
+    pub fn get_record_ranking(&self) -> RecordRanking {
+        self.record_ranking
+    }
+
+    pub fn set_play_mode(&mut self, play_mode: PlayMode) {
+        self.play_mode = play_mode;
+    }
+
+    pub fn get_play_mode(&self) -> PlayMode {
+        self.play_mode
+    }
+
     pub fn unlocked_levels(&self) -> usize {
         self.stock_records.len()
     }
@@ -1,55 +1,68 @@
-use bevy::{app::Plugin as BevyPlugin, prelude::*};
+use bevy::{app::Plugin as BevyPlugin, input::keyboard::KeyboardInput, prelude::*};
 use bevy_ui_bits::RootMarker;
 
 use crate::{
     assets::prelude::*,
     character::Character,
     input::InputEvent,
-    level::{Brush, BrushSprite, EntityComponent, LevelValidity},
+    level::{
+        Brush, BrushSprite, EntityComponent, LevelResource, fit_camera_to_level, reset_camera,
+    },
     state::GameState,
 };
 
 use super::systems::{
-    apply_brush_to_level, blink_tile, check_total_custom_levels, check_validity, handle_input,
-    play_sfx, setup_level, update_brush_sprite, update_character_position, update_map,
+    apply_brush_to_level, blink_tile, check_validity, handle_input, play_sfx, resize_level,
+    setup_level, update_brush_sprite, update_character_position, update_map,
 };
 
 pub struct Plugin;
 
 impl BevyPlugin for Plugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(LevelValidity::default())
-            .add_systems(
-                OnEnter(GameState::Editor),
-                (
-                    check_total_custom_levels,
-                    super::ui::spawn,
-                    Brush::insert,
-                    setup_level,
-                ),
+        app.add_systems(
+            OnEnter(GameState::Editor),
+            (
+                super::ui::spawn,
+                Brush::insert,
+                (setup_level, fit_camera_to_level).chain(),
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                handle_input.run_if(on_event::<InputEvent>),
+                blink_tile,
+                apply_brush_to_level,
+                update_character_position,
+                update_map,
+                update_brush_sprite,
+                check_validity,
+                play_sfx.run_if(on_event::<InputEvent>),
             )
-            .add_systems(
-                Update,
-                (
-                    handle_input.run_if(on_event::<InputEvent>),
-                    blink_tile,
-                    apply_brush_to_level,
-                    update_character_position,
-                    update_map,
-                    update_brush_sprite,
-                    check_validity,
-                    play_sfx.run_if(on_event::<InputEvent>),
-                )
-                    .run_if(in_state(GameState::Editor)),
+                .run_if(in_state(GameState::Editor)),
+        )
+        .add_systems(
+            Update,
+            (
+                resize_level.run_if(on_event::<KeyboardInput>),
+                fit_camera_to_level.run_if(resource_changed::<LevelResource>),
             )
-            .add_systems(
-                OnExit(GameState::Editor),
-                (
-                    cleanup::<RootMarker>,
-                    cleanup::<Character>,
-                    cleanup::<EntityComponent>,
-                    cleanup::<BrushSprite>,
-                ),
-            );
+                .chain()
+                // the old tiles are still around until the end of the frame
+                .after(blink_tile)
+                .after(update_map)
+                .run_if(in_state(GameState::Editor)),
+        )
+        .add_systems(
+            OnExit(GameState::Editor),
+            (
+                reset_camera,
+                cleanup::<RootMarker>,
+                cleanup::<Character>,
+                cleanup::<EntityComponent>,
+                cleanup::<BrushSprite>,
+            ),
+        );
     }
 }
@@ -1,11 +1,11 @@
-use bevy::prelude::*;
+use bevy::{input::keyboard::KeyboardInput, prelude::*};
 use bevy_kira_audio::{AudioChannel, AudioControl};
 
 use bevy_ui_bits::DynamicTextData;
 use game_core::{
     input::{Action, Input},
     level::LevelKind,
-    map::MapEntity,
+    map::{MAX_MAP_COLS, MAX_MAP_ROWS, MapEntity, MapPosition},
 };
 
 use crate::{
@@ -14,37 +14,21 @@ use crate::{
     input::InputEvent,
     level::{
         Brush, BrushEntity, BrushSprite, EntityComponent, LevelInsertionEvent, LevelResource,
-        LevelValidity, TOTAL_CUSTOM_LEVELS, apply_position_to_translation,
+        apply_position_to_translation, entity_tint,
     },
-    save_file::SaveFile,
     state::GameStateTransitionEvent,
 };
 
-use super::ui::VALID_ID;
+use super::ui::{PROBLEMS_ID, VALID_ID};
 
-pub fn check_total_custom_levels(
-    save_file: Res<SaveFile>,
-    mut scene_transition_event_writer: EventWriter<GameStateTransitionEvent>,
-) {
-    if save_file.number_custom_levels() == TOTAL_CUSTOM_LEVELS {
-        scene_transition_event_writer.write(GameStateTransitionEvent::limit());
-    }
-}
-
-pub fn setup_level(
-    mut commands: Commands,
-    images: Res<Images>,
-    mut level_validity: ResMut<LevelValidity>,
-) {
+pub fn setup_level(mut commands: Commands, images: Res<Images>) {
     let mut level = LevelResource::default();
     level.spawn(&mut commands, &images);
     commands.insert_resource(level);
-    level_validity.reset();
 }
 
 pub fn handle_input(
     level: Res<LevelResource>,
-    level_validity: Res<LevelValidity>,
     mut brush: ResMut<Brush>,
     mut game_state_event_writer: EventWriter<GameStateTransitionEvent>,
     mut level_insertion_event_writer: EventWriter<LevelInsertionEvent>,
@@ -53,13 +37,14 @@ pub fn handle_input(
     for input_event in input_event_reader.read() {
         match **input_event {
             Input::Direction(direction) => {
-                brush.position.update(&direction);
+                brush.position.update(&direction, level.state().map());
             }
             Input::Action(Action::Toggle) => brush.cycle(),
+            Input::Action(Action::Color) => brush.cycle_color(),
             Input::Action(Action::Select) => {
-                if level_validity.zones > 0 && level_validity.zones == level_validity.boxes {
+                if level.state().is_valid() {
                     level_insertion_event_writer.write(LevelInsertionEvent::new(
-                        LevelKind::Editable(*level.state()),
+                        LevelKind::Editable(level.state().clone()),
                     ));
                 }
             }
@@ -71,8 +56,54 @@ pub fn handle_input(
     }
 }
 
+/// The directions move the brush, so the map is resized with their own keys, from
+/// its right and bottom edges
+pub fn resize_level(
+    mut commands: Commands,
+    images: Res<Images>,
+    mut level: ResMut<LevelResource>,
+    mut brush: ResMut<Brush>,
+    mut keyboard_input_events: EventReader<KeyboardInput>,
+    entities: Query<Entity, With<EntityComponent>>,
+) {
+    let map = level.state().map();
+    let (mut width, mut height) = (map.width(), map.height());
+
+    for event in keyboard_input_events.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+        match event.key_code {
+            KeyCode::BracketLeft => width = width.saturating_sub(1).max(1),
+            KeyCode::BracketRight => width = (width + 1).min(MAX_MAP_COLS),
+            KeyCode::Minus => height = height.saturating_sub(1).max(1),
+            KeyCode::Equal => height = (height + 1).min(MAX_MAP_ROWS),
+            _ => (),
+        }
+    }
+
+    if width == map.width() && height == map.height() {
+        return;
+    }
+
+    level.resize(width, height);
+    brush.position = MapPosition::new(
+        brush.position.x().min(width - 1),
+        brush.position.y().min(height - 1),
+    );
+    brush.plate = brush
+        .plate
+        .filter(|plate| level.state().map().contains(plate));
+
+    for entity in &entities {
+        commands.entity(entity).despawn();
+    }
+    level.spawn_map(&mut commands, &images);
+}
+
 pub fn blink_tile(
     time: Res<Time>,
+    level: Res<LevelResource>,
     mut brush: ResMut<Brush>,
     mut entity_query: Query<(&mut Sprite, &EntityComponent), With<EntityComponent>>,
 ) {
@@ -80,91 +111,102 @@ pub fn blink_tile(
 
     if brush.blink_timer.just_finished() {
         for (mut sprite, position) in &mut entity_query {
+            let tint = entity_tint(level.state(), position);
             if position.x() == brush.position.x() && position.y() == brush.position.y() {
                 if sprite.color == crate::theme::PRIMARY {
-                    sprite.color = crate::theme::LIGHT;
+                    sprite.color = tint;
                 } else {
                     sprite.color = crate::theme::PRIMARY;
                 }
             } else {
-                sprite.color = crate::theme::LIGHT;
+                sprite.color = tint;
             }
         }
     }
 }
 
-pub fn apply_brush_to_level(
-    brush: Res<Brush>,
-    mut level: ResMut<LevelResource>,
-    mut level_validity: ResMut<LevelValidity>,
-) {
-    if matches!(brush.entity, BrushEntity::Character) {
-        if matches!(
-            level.get_entity(&brush.position),
-            MapEntity::F | MapEntity::Z
-        ) {
-            level.move_character(brush.position);
+pub fn apply_brush_to_level(mut brush: ResMut<Brush>, mut level: ResMut<LevelResource>) {
+    let current_entity = *level.get_entity(&brush.position);
+    let entity = match brush.entity {
+        BrushEntity::Character => {
+            if matches!(current_entity, MapEntity::F | MapEntity::Z) {
+                level.move_character(brush.position);
+            }
+            return;
         }
-    } else if level.character_position() == brush.position {
-        level.set_entity(
-            &brush.position,
-            match brush.entity {
-                BrushEntity::Floor => MapEntity::F,
-                BrushEntity::Zone => MapEntity::Z,
-                _ => return,
-            },
-        );
+        BrushEntity::Floor => MapEntity::F,
+        BrushEntity::Void => MapEntity::V,
+        BrushEntity::Zone => MapEntity::Z,
+        BrushEntity::BoxInFloor => MapEntity::B,
+        BrushEntity::BoxInZone => MapEntity::P,
+        BrushEntity::Ice => MapEntity::I,
+        BrushEntity::BoxInIce => MapEntity::J,
+        BrushEntity::Teleporter => MapEntity::T,
+        BrushEntity::Plate => MapEntity::S,
+        BrushEntity::Door => MapEntity::D,
+        BrushEntity::Hole => MapEntity::H,
+    };
 
-        if matches!(brush.entity, BrushEntity::Floor)
-            && matches!(level.get_entity(&brush.position), MapEntity::Z)
-        {
-            level_validity.zones -= 1;
-            level.decrement_remaining_zones();
-        } else if matches!(brush.entity, BrushEntity::Zone)
-            && matches!(level.get_entity(&brush.position), MapEntity::F)
-        {
-            level_validity.zones += 1;
-            level.increment_remaining_zones();
-        }
-    } else {
-        if matches!(
-            brush.entity,
-            BrushEntity::Floor | BrushEntity::Void | BrushEntity::BoxInFloor
-        ) && matches!(level.get_entity(&brush.position), MapEntity::Z)
-        {
-            level_validity.zones -= 1;
-            level.decrement_remaining_zones();
-        } else if matches!(brush.entity, BrushEntity::Zone)
-            && matches!(
-                level.get_entity(&brush.position),
-                MapEntity::F | MapEntity::V | MapEntity::B
-            )
-        {
-            level_validity.zones += 1;
-            level.increment_remaining_zones();
+    // the character can only stand on the floor or on a zone
+    if level.character_position() == brush.position
+        && !matches!(entity, MapEntity::F | MapEntity::Z)
+    {
+        return;
+    }
+
+    if current_entity != entity {
+        // without colors the zone counter only depends on the entities
+        level.set_box_color(brush.position, 0);
+        level.set_zone_color(brush.position, 0);
+
+        match (current_entity, entity) {
+            (MapEntity::Z, _) => level.decrement_remaining_zones(),
+            (_, MapEntity::Z) => level.increment_remaining_zones(),
+            _ => (),
         }
 
-        if !matches!(brush.entity, BrushEntity::BoxInFloor)
-            && matches!(level.get_entity(&brush.position), MapEntity::B)
-        {
-            level_validity.boxes -= 1;
-        } else if matches!(brush.entity, BrushEntity::BoxInFloor)
-            && !matches!(level.get_entity(&brush.position), MapEntity::B)
-        {
-            level_validity.boxes += 1;
+        level.unlink_teleporter(&brush.position);
+        level.unlink_switch(&brush.position);
+        level.set_entity(&brush.position, entity);
+
+        // a new teleporter is linked to the one waiting for a partner, if any
+        if entity.is_teleporter() {
+            let map = level.state().map();
+            let unpaired = map.positions().find(|position| {
+                *position != brush.position
+                    && map[position].is_teleporter()
+                    && level.teleporter_partner(position).is_none()
+            });
+            if let Some(partner) = unpaired {
+                level.link_teleporters(brush.position, partner);
+            }
         }
+    }
+
+    let box_color = if entity.has_box() { brush.color } else { 0 };
+    if level.state().box_color(&brush.position) != box_color {
+        level.set_box_color(brush.position, box_color);
+    }
+    let zone_color = if matches!(entity, MapEntity::Z | MapEntity::P) {
+        brush.color
+    } else {
+        0
+    };
+    if level.state().zone_color(&brush.position) != zone_color {
+        level.set_zone_color(brush.position, zone_color);
+    }
 
-        level.set_entity(
-            &brush.position,
-            match brush.entity {
-                BrushEntity::Floor => MapEntity::F,
-                BrushEntity::Void => MapEntity::V,
-                BrushEntity::Zone => MapEntity::Z,
-                BrushEntity::BoxInFloor => MapEntity::B,
-                BrushEntity::BoxInZone => MapEntity::P,
-                BrushEntity::Character => return,
-            },
-        );
+    // painting over a plate picks it, and the doors painted next are opened by it
+    match entity {
+        MapEntity::S => brush.plate = Some(brush.position),
+        MapEntity::D => {
+            if let Some(plate) = brush.plate
+                && level.get_entity(&plate).is_plate()
+            {
+                level.link_switch(plate, brush.position);
+            }
+        }
+        _ => (),
     }
 }
 
@@ -189,32 +231,47 @@ pub fn update_brush_sprite(
     transform.translation.y += 20.0;
     transform.translation.z = 20.0;
 
+    sprite.color = crate::theme::tint(brush.color);
     sprite.image = match brush.entity {
         BrushEntity::Floor => images.brush_floor.clone(),
         BrushEntity::Void => images.brush_void.clone(),
         BrushEntity::Zone => images.brush_zone.clone(),
         BrushEntity::BoxInFloor => images.brush_box.clone(),
         BrushEntity::BoxInZone => images.brush_placed_box.clone(),
+        BrushEntity::Ice => images.brush_ice.clone(),
+        BrushEntity::BoxInIce => images.brush_box_on_ice.clone(),
+        BrushEntity::Teleporter => images.brush_teleporter.clone(),
+        BrushEntity::Plate => images.brush_plate.clone(),
+        BrushEntity::Door => images.brush_door.clone(),
+        BrushEntity::Hole => images.brush_hole.clone(),
         BrushEntity::Character => images.brush_character.clone(),
     };
 }
 
 pub fn check_validity(
-    level_validity: Res<LevelValidity>,
+    level: Res<LevelResource>,
     mut writer: TextUiWriter,
-    mut texts: Query<(Entity, &DynamicTextData)>,
+    texts: Query<(Entity, &DynamicTextData)>,
 ) {
-    let (entity, data) = texts.single_mut().unwrap();
-    *writer.text(entity, 1) = match data.id {
-        VALID_ID => {
-            if level_validity.zones > 0 && level_validity.zones == level_validity.boxes {
-                "YES".to_string()
-            } else {
-                "NO".to_string()
+    let problems = level.state().validate();
+
+    for (entity, data) in texts {
+        *writer.text(entity, 1) = match data.id {
+            VALID_ID => {
+                if problems.is_empty() {
+                    "YES".to_string()
+                } else {
+                    "NO".to_string()
+                }
             }
-        }
-        _ => unreachable!("The text id does not exists"),
-    };
+            PROBLEMS_ID => problems
+                .iter()
+                .map(|problem| problem.to_string())
+                .collect::<Vec<String>>()
+                .join("\n"),
+            _ => unreachable!("The text id does not exists"),
+        };
+    }
 }
 
 pub fn update_map(
@@ -230,6 +287,15 @@ pub fn update_map(
             MapEntity::Z => images.entity_zone.clone(),
             MapEntity::B => images.entity_box.clone(),
             MapEntity::P => images.entity_placed_box.clone(),
+            MapEntity::I => images.entity_ice.clone(),
+            MapEntity::J => images.entity_box_on_ice.clone(),
+            MapEntity::T => images.entity_teleporter.clone(),
+            MapEntity::U => images.entity_box_on_teleporter.clone(),
+            MapEntity::S => images.entity_plate.clone(),
+            MapEntity::Q => images.entity_box_on_plate.clone(),
+            MapEntity::D if level.state().is_door_open(position) => images.entity_open_door.clone(),
+            MapEntity::D => images.entity_door.clone(),
+            MapEntity::H => images.entity_hole.clone(),
         };
         apply_position_to_translation(position, &mut transform.translation);
     }
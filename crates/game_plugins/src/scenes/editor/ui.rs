@@ -4,6 +4,7 @@ use bevy_ui_bits::{Container, DynamicTextBuilder, Root, SimpleText, UiText};
 use crate::assets::prelude::*;
 
 pub const VALID_ID: usize = 0;
+pub const PROBLEMS_ID: usize = 1;
 
 pub fn spawn(mut commands: Commands, fonts: Res<Fonts>) {
     let font = fonts.primary();
@@ -35,12 +36,15 @@ pub fn spawn(mut commands: Commands, fonts: Res<Fonts>) {
     let subtitle =
         SimpleText::small("Custom Level Creation", font).color(crate::theme::SECONDARY.into());
     let valid = DynamicTextBuilder::medium("Valid: ", font).id(VALID_ID);
-    let instructions = SimpleText::small(
-        "A valid level has at least one box and a zone per box",
-        font,
-    );
+    let problems = DynamicTextBuilder::small("", font)
+        .id(PROBLEMS_ID)
+        .color(crate::theme::SECONDARY.into());
     let toggle =
         SimpleText::small("(ENTER) - Toggle Entity", font).color(crate::theme::PRIMARY.into());
+    let color = SimpleText::small("(C) - Change Color", font).color(crate::theme::PRIMARY.into());
+    let width = SimpleText::small("([ ]) - Change Width", font).color(crate::theme::PRIMARY.into());
+    let height =
+        SimpleText::small("(- =) - Change Height", font).color(crate::theme::PRIMARY.into());
     let playtest =
         SimpleText::small("(SPACE) - Playtest Level", font).color(crate::theme::PRIMARY.into());
 
@@ -51,13 +55,13 @@ pub fn spawn(mut commands: Commands, fonts: Res<Fonts>) {
                 top,
                 children![
                     (top_left, children![title, subtitle]),
-                    (top_right, children![valid.build(), instructions])
+                    (top_right, children![valid.build(), problems.build()])
                 ]
             ),
             (
                 bottom,
                 children![
-                    (bottom_left, children![toggle]),
+                    (bottom_left, children![toggle, color, width, height]),
                     (bottom_right, children![playtest])
                 ]
             )
@@ -0,0 +1,30 @@
+use bevy::{app::Plugin as BevyPlugin, prelude::*};
+use bevy_ui_bits::RootMarker;
+
+use crate::{assets::prelude::*, input::InputEvent, state::GameState};
+
+use super::systems::{TextCursor, clear_keyboard_input, handle_input, handle_text_input};
+
+pub struct Plugin;
+
+impl BevyPlugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TextCursor {
+            blink_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+            blink_toggle: true,
+        })
+        .add_systems(
+            OnEnter(GameState::Import),
+            (clear_keyboard_input, super::ui::spawn),
+        )
+        .add_systems(
+            Update,
+            (
+                handle_input.run_if(on_event::<InputEvent>),
+                handle_text_input,
+            )
+                .run_if(in_state(GameState::Import)),
+        )
+        .add_systems(OnExit(GameState::Import), cleanup::<RootMarker>);
+    }
+}
@@ -0,0 +1,171 @@
+use bevy::{
+    input::keyboard::{Key, KeyboardInput},
+    prelude::*,
+};
+use bevy_kira_audio::{AudioChannel, AudioControl};
+
+use bevy_ui_bits::DynamicTextData;
+use game_core::{
+    input::{Action, Input},
+    level::{LevelKind, LevelState, PlayMode},
+};
+
+use crate::{
+    assets::prelude::*,
+    clipboard::Clipboard,
+    input::InputEvent,
+    level::LevelResource,
+    save_file::SaveFile,
+    state::{GameStateTransitionEvent, SelectionKind},
+};
+
+use super::ui::{CODE_ERROR_ID, LEVEL_CODE_ID};
+
+/// Only the end of longer codes fits on the screen
+const VISIBLE_CODE_LENGTH: usize = 48;
+
+/// Held with V to paste, the command key is the one used on macOS
+const PASTE_MODIFIERS: [KeyCode; 4] = [
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::SuperLeft,
+    KeyCode::SuperRight,
+];
+
+#[derive(Resource)]
+pub struct TextCursor {
+    pub blink_timer: Timer,
+    pub blink_toggle: bool,
+}
+
+/// The key that opened the scene is not part of the code
+pub fn clear_keyboard_input(mut keyboard_input_events: ResMut<Events<KeyboardInput>>) {
+    keyboard_input_events.clear();
+}
+
+pub fn handle_input(
+    mut game_state_event_writer: EventWriter<GameStateTransitionEvent>,
+    mut input_event_reader: EventReader<InputEvent>,
+) {
+    for input_event in input_event_reader.read() {
+        if matches!(**input_event, Input::Action(Action::Exit)) {
+            game_state_event_writer
+                .write(GameStateTransitionEvent::selection(SelectionKind::Custom));
+        }
+    }
+}
+
+/// A valid code goes through the passed scene, where the level gets its name and is saved
+/// like the ones made in the editor
+#[allow(clippy::too_many_arguments)]
+pub fn handle_text_input(
+    mut commands: Commands,
+    time: Res<Time>,
+    save_file: Res<SaveFile>,
+    mut game_state_event_writer: EventWriter<GameStateTransitionEvent>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut keyboard_input_events: EventReader<KeyboardInput>,
+    mut clipboard: NonSendMut<Clipboard>,
+    mut text_cursor: ResMut<TextCursor>,
+    mut writer: TextUiWriter,
+    texts: Query<(Entity, &DynamicTextData)>,
+    mut level_code: Local<String>,
+    sounds: Res<Sounds>,
+    sfx: Res<AudioChannel<Sfx>>,
+) {
+    if text_cursor.blink_timer.tick(time.delta()).just_finished() {
+        text_cursor.blink_toggle = !text_cursor.blink_toggle;
+    }
+
+    let mut error = None;
+    let mut changed = false;
+    for event in keyboard_input_events.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+
+        // codes are usually shared as text, so they are pasted whole
+        if event.key_code == KeyCode::KeyV && keys.any_pressed(PASTE_MODIFIERS) {
+            if let Some(text) = clipboard.get_text() {
+                sfx.play(sounds.sfx_move_character.clone());
+                level_code.extend(text.chars().filter(|character| !character.is_whitespace()));
+                changed = true;
+            }
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Character(character) => {
+                if character
+                    .chars()
+                    .all(|character| character.is_ascii_alphanumeric() || "-_".contains(character))
+                {
+                    sfx.play(sounds.sfx_move_character.clone());
+                    level_code.push_str(character);
+                    changed = true;
+                }
+            }
+            Key::Backspace => {
+                sfx.play(sounds.sfx_undo_move.clone());
+                level_code.pop();
+                changed = true;
+            }
+            Key::Escape => {
+                *level_code = String::new();
+                changed = true;
+            }
+            Key::Enter => {
+                match LevelState::from_code(&level_code)
+                    .map_err(|error| error.to_string())
+                    .and_then(|state| match state.validate().first() {
+                        Some(problem) => Err(problem.to_string()),
+                        None => Ok(state),
+                    }) {
+                    Ok(state) => {
+                        sfx.play(sounds.sfx_set_zone.clone());
+                        commands.insert_resource(LevelResource::new(
+                            LevelKind::Editable(state.clone()),
+                            state,
+                            save_file.get_undo_policy(),
+                            PlayMode::default(),
+                        ));
+                        *level_code = String::new();
+                        game_state_event_writer.write(GameStateTransitionEvent::passed());
+                    }
+                    Err(message) => {
+                        sfx.play(sounds.sfx_push_box.clone());
+                        error = Some(message);
+                    }
+                }
+                changed = true;
+            }
+            _ => {}
+        }
+    }
+
+    for (entity, data) in texts {
+        match data.id {
+            LEVEL_CODE_ID => {
+                *writer.color(entity, 1) = TextColor(if text_cursor.blink_toggle {
+                    crate::theme::TRANSPARENT
+                } else {
+                    crate::theme::SECONDARY
+                });
+                if changed {
+                    let hidden = level_code.len().saturating_sub(VISIBLE_CODE_LENGTH);
+                    *writer.text(entity, 0) = if hidden > 0 {
+                        format!("...{}", &level_code[hidden..])
+                    } else {
+                        level_code.to_string()
+                    };
+                }
+            }
+            CODE_ERROR_ID => {
+                if changed {
+                    *writer.text(entity, 1) = error.clone().unwrap_or_default();
+                }
+            }
+            _ => unreachable!("The text id does not exists"),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use bevy_ui_bits::{Container, DynamicTextBuilder, Root, SimpleText, UiText};
+
+use crate::assets::prelude::*;
+
+pub const LEVEL_CODE_ID: usize = 1;
+pub const CODE_ERROR_ID: usize = 2;
+
+pub fn spawn(mut commands: Commands, fonts: Res<Fonts>) {
+    let font = fonts.primary();
+
+    let root = Root::new();
+    let center = Container::size(Val::Px(600.0), Val::Px(300.0)).justify_between();
+    let level_code = Container::new();
+
+    let title = SimpleText::large("Import a Level", font).color(crate::theme::PRIMARY.into());
+    let level_code_title = SimpleText::medium("Type the level code:", font);
+    let level_code_input = DynamicTextBuilder::small("", font)
+        .id(LEVEL_CODE_ID)
+        .color(crate::theme::SECONDARY.into())
+        .initial_dynamic_text("_");
+    let code_error = DynamicTextBuilder::small("", font)
+        .id(CODE_ERROR_ID)
+        .color(crate::theme::PRIMARY.into());
+    let press_button =
+        SimpleText::small("Press ENTER to import the level, CTRL+V to paste it", font);
+
+    commands.spawn((
+        root,
+        children![(
+            center,
+            children![
+                title,
+                (
+                    level_code,
+                    children![
+                        level_code_title,
+                        level_code_input.build(),
+                        code_error.build()
+                    ]
+                ),
+                press_button
+            ]
+        )],
+    ));
+}
@@ -1,19 +1,44 @@
+use std::{collections::VecDeque, time::Duration};
+
 use bevy::{app::Plugin as BevyPlugin, prelude::*};
 use bevy_ui_bits::RootMarker;
+use game_core::{input::Direction, map::MapPosition};
 
 use crate::{
     assets::prelude::*,
     character::{Character, CharacterAnimation},
     input::InputEvent,
-    level::EntityComponent,
+    level::{EntityComponent, fit_camera_to_level, reset_camera},
     state::GameState,
 };
 
 use super::systems::{
-    check_lever_timer_just_finished, handle_input, spawn_level, update_character_position,
-    update_counters, update_level_state, update_map,
+    check_lever_timer_just_finished, handle_input, plan_moves, play_planned_moves,
+    reset_planned_moves, spawn_level, update_character_position, update_counters,
+    update_level_state, update_map,
 };
 
+const PLANNED_MOVE_DURATION: Duration = Duration::from_millis(120);
+
+/// Moves left after a click, a tap or a drag, played one at a time
+#[derive(Resource)]
+pub(super) struct PlannedMoves {
+    pub directions: VecDeque<Direction>,
+    /// Box under the pointer since it was pressed
+    pub grabbed: Option<MapPosition>,
+    pub timer: Timer,
+}
+
+impl Default for PlannedMoves {
+    fn default() -> Self {
+        PlannedMoves {
+            directions: VecDeque::new(),
+            grabbed: None,
+            timer: Timer::new(PLANNED_MOVE_DURATION, TimerMode::Repeating),
+        }
+    }
+}
+
 pub struct Plugin;
 
 impl BevyPlugin for Plugin {
@@ -23,12 +48,16 @@ impl BevyPlugin for Plugin {
             (
                 super::ui::spawn,
                 spawn_level,
+                reset_planned_moves,
+                fit_camera_to_level,
                 CharacterAnimation::insert_level_character_animation,
             ),
         )
         .add_systems(
             Update,
             (
+                plan_moves,
+                play_planned_moves,
                 handle_input.run_if(on_event::<InputEvent>),
                 CharacterAnimation::update_level_character_animation,
                 update_character_position,
@@ -46,6 +75,7 @@ impl BevyPlugin for Plugin {
                 cleanup::<RootMarker>,
                 cleanup::<Character>,
                 cleanup::<EntityComponent>,
+                reset_camera,
             ),
         );
     }
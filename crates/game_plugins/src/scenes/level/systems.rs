@@ -1,8 +1,9 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, window::PrimaryWindow};
 use bevy_kira_audio::{AudioChannel, AudioControl};
 
 use bevy_ui_bits::DynamicTextData;
 use game_core::{
+    input::Input,
     level::{LevelKind, LevelUpdate},
     map::MapEntity,
 };
@@ -11,16 +12,111 @@ use crate::{
     assets::prelude::*,
     character::Character,
     input::InputEvent,
-    level::{EntityComponent, LevelResource, apply_position_to_translation},
+    level::{
+        EntityComponent, LevelResource, apply_position_to_translation, entity_tint,
+        translation_to_position,
+    },
     state::{GameStateTransitionEvent, SelectionKind},
 };
 
-use super::ui::{MOVES_COUNTER_ID, STOPWATCH_COUNTER_ID, UNDOS_COUNTER_ID};
+use super::{
+    plugin::PlannedMoves,
+    ui::{
+        DEADLOCK_WARNING_ID, MOVES_COUNTER_ID, PUSHES_COUNTER_ID, STOPWATCH_COUNTER_ID,
+        UNDOS_COUNTER_ID,
+    },
+};
 
 pub fn spawn_level(mut commands: Commands, mut level: ResMut<LevelResource>, images: Res<Images>) {
     level.spawn(&mut commands, &images);
 }
 
+pub fn reset_planned_moves(mut commands: Commands) {
+    commands.insert_resource(PlannedMoves::default());
+}
+
+/// Clicking or tapping a tile walks the character there, dragging a box onto a tile
+/// pushes it there, and any key cancels the moves left
+pub fn plan_moves(
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    level: Res<LevelResource>,
+    mut planned_moves: ResMut<PlannedMoves>,
+) {
+    if keyboard.get_just_pressed().next().is_some() {
+        planned_moves.directions.clear();
+        planned_moves.grabbed = None;
+        return;
+    }
+
+    let cursor = || window.single().ok().and_then(Window::cursor_position);
+    let pressed = if mouse.just_pressed(MouseButton::Left) {
+        cursor()
+    } else {
+        touches
+            .iter_just_pressed()
+            .next()
+            .map(|touch| touch.position())
+    };
+    let released = if mouse.just_released(MouseButton::Left) {
+        cursor()
+    } else {
+        touches
+            .iter_just_released()
+            .next()
+            .map(|touch| touch.position())
+    };
+    let Ok((camera, camera_transform)) = camera.single() else {
+        return;
+    };
+    let to_position = |cursor: Vec2| {
+        camera
+            .viewport_to_world_2d(camera_transform, cursor)
+            .ok()
+            .and_then(|point| translation_to_position(point, level.state().map()))
+    };
+
+    if let Some(position) = pressed.and_then(to_position) {
+        planned_moves.grabbed = level.get_entity(&position).has_box().then_some(position);
+    }
+
+    if let Some(cursor) = released {
+        let grabbed = planned_moves.grabbed.take();
+        let Some(position) = to_position(cursor) else {
+            return;
+        };
+        let directions = match grabbed {
+            Some(grabbed) if grabbed != position => level.push_path(&grabbed, &position),
+            Some(_) => None,
+            None => level.path_to(&position),
+        };
+        if let Some(directions) = directions {
+            planned_moves.directions = directions.into();
+            planned_moves.timer.reset();
+        }
+    }
+}
+
+/// Every planned move goes through the level like the ones from the keyboard
+pub fn play_planned_moves(
+    time: Res<Time>,
+    mut planned_moves: ResMut<PlannedMoves>,
+    mut input_event_writer: EventWriter<InputEvent>,
+) {
+    if planned_moves.directions.is_empty()
+        || !planned_moves.timer.tick(time.delta()).just_finished()
+    {
+        return;
+    }
+
+    if let Some(direction) = planned_moves.directions.pop_front() {
+        input_event_writer.write(Input::Direction(direction).into());
+    }
+}
+
 pub fn handle_input(
     mut game_state_event_writer: EventWriter<GameStateTransitionEvent>,
     mut input_event_reader: EventReader<InputEvent>,
@@ -35,7 +131,10 @@ pub fn handle_input(
     for input_event in input_event_reader.read() {
         if let Some(update) = level.update(input_event) {
             match update {
-                LevelUpdate::PushBox => {
+                LevelUpdate::PushBox
+                | LevelUpdate::PullBox
+                | LevelUpdate::SlideBox
+                | LevelUpdate::TeleportBox => {
                     sfx.play(sounds.sfx_move_character.clone());
                     sfx.play(sounds.sfx_push_box.clone());
                 }
@@ -44,24 +143,42 @@ pub fn handle_input(
                     sfx.play(sounds.sfx_push_box.clone());
                     sfx.play(sounds.sfx_set_zone.clone());
                 }
-                LevelUpdate::MoveCharacter => {
+                LevelUpdate::MoveCharacter
+                | LevelUpdate::SlideCharacter
+                | LevelUpdate::TeleportCharacter => {
+                    sfx.play(sounds.sfx_move_character.clone());
+                }
+                LevelUpdate::FillHole => {
+                    sfx.play(sounds.sfx_move_character.clone());
+                    sfx.play(sounds.sfx_push_box.clone());
+                    sfx.play(sounds.sfx_undo_move.clone());
+                }
+                LevelUpdate::DoorOpened => {
+                    sfx.play(sounds.sfx_move_character.clone());
+                    sfx.play(sounds.sfx_set_zone.clone());
+                }
+                LevelUpdate::DoorClosed => {
                     sfx.play(sounds.sfx_move_character.clone());
+                    sfx.play(sounds.sfx_push_box.clone());
                 }
                 LevelUpdate::UndoMove => {
                     sfx.play(sounds.sfx_undo_move.clone());
                 }
+                LevelUpdate::RedoMove => {
+                    sfx.play(sounds.sfx_move_character.clone());
+                }
                 LevelUpdate::Reload => {
                     sfx.play(sounds.sfx_reload_level.clone());
                 }
                 LevelUpdate::Exit => {
                     sfx.play(sounds.sfx_push_box.clone());
-                    game_state_event_writer.write(GameStateTransitionEvent::selection(
-                        if level.is_stock() {
-                            SelectionKind::Stock
-                        } else {
-                            SelectionKind::Custom
-                        },
-                    ));
+                    game_state_event_writer.write(GameStateTransitionEvent::selection(match level
+                        .kind()
+                    {
+                        LevelKind::Stock(_) => SelectionKind::Stock,
+                        LevelKind::Pack(..) => SelectionKind::Pack,
+                        _ => SelectionKind::Custom,
+                    }));
                 }
             }
         }
@@ -87,8 +204,16 @@ pub fn update_counters(
     for (entity, data) in texts {
         *writer.text(entity, 1) = match data.id {
             MOVES_COUNTER_ID => level.moves_string(),
+            PUSHES_COUNTER_ID => level.pushes_string(),
             UNDOS_COUNTER_ID => level.undos_string(),
             STOPWATCH_COUNTER_ID => level.time_string(),
+            DEADLOCK_WARNING_ID => {
+                if level.is_deadlocked() {
+                    "Stuck! Undo or reload".to_string()
+                } else {
+                    String::new()
+                }
+            }
             _ => unreachable!("The counter id does not exists"),
         };
     }
@@ -97,6 +222,7 @@ pub fn update_counters(
 pub fn update_map(
     level: Res<LevelResource>,
     images: Res<Images>,
+    planned_moves: Res<PlannedMoves>,
     mut query: Query<(&mut Sprite, &mut Transform, &EntityComponent)>,
 ) {
     for (mut sprite, mut transform, position) in &mut query {
@@ -107,6 +233,21 @@ pub fn update_map(
             MapEntity::Z => images.entity_zone.clone(),
             MapEntity::B => images.entity_box.clone(),
             MapEntity::P => images.entity_placed_box.clone(),
+            MapEntity::I => images.entity_ice.clone(),
+            MapEntity::J => images.entity_box_on_ice.clone(),
+            MapEntity::T => images.entity_teleporter.clone(),
+            MapEntity::U => images.entity_box_on_teleporter.clone(),
+            MapEntity::S => images.entity_plate.clone(),
+            MapEntity::Q => images.entity_box_on_plate.clone(),
+            MapEntity::D if level.state().is_door_open(position) => images.entity_open_door.clone(),
+            MapEntity::D => images.entity_door.clone(),
+            MapEntity::H => images.entity_hole.clone(),
+        };
+        // the grabbed box stands out until it is dropped
+        sprite.color = if planned_moves.grabbed == Some(**position) {
+            crate::theme::PRIMARY
+        } else {
+            entity_tint(level.state(), position)
         };
         apply_position_to_translation(position, &mut transform.translation);
     }
@@ -122,7 +263,10 @@ pub fn check_lever_timer_just_finished(
 ) {
     if level.finished() {
         match level.kind() {
-            LevelKind::Stock(_) | LevelKind::Custom(_) => {
+            LevelKind::Stock(_)
+            | LevelKind::Custom(_)
+            | LevelKind::Pack(..)
+            | LevelKind::Generated(_) => {
                 scene_transition_event_writer.write(GameStateTransitionEvent::win());
             }
             LevelKind::Editable(_) => {
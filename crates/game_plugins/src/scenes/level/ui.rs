@@ -6,6 +6,8 @@ use crate::{assets::prelude::*, level::LevelResource};
 pub const STOPWATCH_COUNTER_ID: usize = 0;
 pub const MOVES_COUNTER_ID: usize = 1;
 pub const UNDOS_COUNTER_ID: usize = 2;
+pub const DEADLOCK_WARNING_ID: usize = 3;
+pub const PUSHES_COUNTER_ID: usize = 4;
 
 pub fn spawn(mut commands: Commands, level: Res<LevelResource>, fonts: Res<Fonts>) {
     let font = fonts.primary();
@@ -41,13 +43,41 @@ pub fn spawn(mut commands: Commands, level: Res<LevelResource>, fonts: Res<Fonts
 
     let stopwatch_housing = Container::size(Val::Px(152.0), Val::Auto).items_start();
 
-    let level_name = SimpleText::medium(&format!("Level {}", level.name()), font);
+    let pack_level = level.pack_level();
+    let level_name = match pack_level {
+        Some(pack_level) if !pack_level.name().is_empty() => pack_level.name().to_string(),
+        _ => format!("Level {}", level.name()),
+    };
+    let level_name = if level.mode().is_pull() {
+        format!("{level_name} (pull)")
+    } else {
+        level_name
+    };
+    let par = pack_level
+        .and_then(|pack_level| pack_level.par_string())
+        .unwrap_or_default();
+    let hint = pack_level
+        .and_then(|pack_level| pack_level.hint())
+        .map(|hint| format!("Hint: {hint}"))
+        .unwrap_or_default();
+    let level_name = SimpleText::medium(&level_name, font);
     let record_new_level =
         SimpleText::small(&record_new_level, font).color(crate::theme::SECONDARY.into());
+    let par = SimpleText::small(&par, font).color(crate::theme::SECONDARY.into());
+    let hint = SimpleText::small(&hint, font);
+    let deadlock_warning = DynamicTextBuilder::small("", font)
+        .id(DEADLOCK_WARNING_ID)
+        .color(crate::theme::PRIMARY.into());
     let stopwatch = DynamicTextBuilder::small("Time: ", font).id(STOPWATCH_COUNTER_ID);
     let moves = DynamicTextBuilder::medium("Moves: ", font).id(MOVES_COUNTER_ID);
+    let pushes = DynamicTextBuilder::medium("Pushes: ", font).id(PUSHES_COUNTER_ID);
     let undos_left = DynamicTextBuilder::medium("Undos: ", font).id(UNDOS_COUNTER_ID);
     let undo = SimpleText::small("(Z) - Undo Movement", font).color(crate::theme::PRIMARY.into());
+    let redo = SimpleText::small("(Y) - Redo Movement", font).color(crate::theme::PRIMARY.into());
+    let undo_push =
+        SimpleText::small("(X) - Undo Last Push", font).color(crate::theme::PRIMARY.into());
+    let undo_all =
+        SimpleText::small("(HOME) - Undo to Start", font).color(crate::theme::PRIMARY.into());
     let reload = SimpleText::small("(F5) - Reload Level", font).color(crate::theme::PRIMARY.into());
     let selection =
         SimpleText::small("(ESC) - Level Selection", font).color(crate::theme::PRIMARY.into());
@@ -58,11 +88,21 @@ pub fn spawn(mut commands: Commands, level: Res<LevelResource>, fonts: Res<Fonts
             (
                 top,
                 children![
-                    (top_left, children![level_name, record_new_level]),
+                    (
+                        top_left,
+                        children![
+                            level_name,
+                            record_new_level,
+                            par,
+                            hint,
+                            deadlock_warning.build()
+                        ]
+                    ),
                     (
                         top_right,
                         children![
                             moves.build(),
+                            pushes.build(),
                             (stopwatch_housing, children![stopwatch.build()])
                         ]
                     )
@@ -72,7 +112,10 @@ pub fn spawn(mut commands: Commands, level: Res<LevelResource>, fonts: Res<Fonts
                 bottom,
                 children![
                     (bottom_left, children![reload, selection]),
-                    (bottom_right, children![undos_left.build(), undo])
+                    (
+                        bottom_right,
+                        children![undos_left.build(), undo, redo, undo_push, undo_all]
+                    )
                 ]
             )
         ],
@@ -1,7 +1,7 @@
 mod editor;
+mod import;
 mod instructions;
 mod level;
-mod limit;
 mod options;
 mod passed;
 mod plugin;
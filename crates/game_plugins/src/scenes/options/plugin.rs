@@ -3,13 +3,20 @@ use bevy_ui_bits::RootMarker;
 
 use crate::{assets::prelude::*, input::InputEvent, state::GameState};
 
-use super::systems::{handle_input, update_dynamic_text};
+use super::{
+    systems::{handle_input, update_dynamic_text},
+    ui,
+};
+
+#[derive(Resource)]
+pub(super) struct SelectedOption(pub usize);
 
 pub struct Plugin;
 
 impl BevyPlugin for Plugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Options), super::ui::spawn)
+        app.insert_resource(SelectedOption(ui::VOLUME_ID))
+            .add_systems(OnEnter(GameState::Options), ui::spawn)
             .add_systems(
                 Update,
                 (
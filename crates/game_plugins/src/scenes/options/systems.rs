@@ -2,32 +2,82 @@ use bevy::prelude::*;
 use bevy_kira_audio::{AudioChannel, AudioControl};
 
 use bevy_ui_bits::DynamicTextData;
-use game_core::input::{Action, Direction, Input};
+use game_core::{
+    input::{Action, Direction, Input},
+    level::UndoPolicy,
+};
 
 use crate::{
     assets::prelude::*, input::InputEvent, save_file::SaveFile, state::GameStateTransitionEvent,
 };
 
-use super::ui::VOLUME_ID;
+use super::{
+    plugin::SelectedOption,
+    ui::{RANKING_ID, UNDOS_ID, VOLUME_ID},
+};
+
+const UNDO_POLICIES: [UndoPolicy; 4] = [
+    UndoPolicy::Limited(4),
+    UndoPolicy::Limited(8),
+    UndoPolicy::Limited(16),
+    UndoPolicy::Unlimited,
+];
 
 pub fn handle_input(
     mut game_state_event_writer: EventWriter<GameStateTransitionEvent>,
     mut input_event_reader: EventReader<InputEvent>,
     mut sounds: ResMut<Sounds>,
     mut save_file: ResMut<SaveFile>,
+    mut selected_option: ResMut<SelectedOption>,
     sfx: Res<AudioChannel<Sfx>>,
 ) {
     for input_event in input_event_reader.read() {
         match **input_event {
+            Input::Direction(Direction::Up) => {
+                sfx.play(sounds.sfx_move_character.clone());
+                selected_option.0 = match selected_option.0 {
+                    VOLUME_ID => RANKING_ID,
+                    UNDOS_ID => VOLUME_ID,
+                    _ => UNDOS_ID,
+                };
+            }
+            Input::Direction(Direction::Down) => {
+                sfx.play(sounds.sfx_move_character.clone());
+                selected_option.0 = match selected_option.0 {
+                    VOLUME_ID => UNDOS_ID,
+                    UNDOS_ID => RANKING_ID,
+                    _ => VOLUME_ID,
+                };
+            }
             Input::Direction(Direction::Left) => {
                 sfx.play(sounds.sfx_move_character.clone());
-                sounds.decrease_volume();
-                save_file.set_volume(sounds.get_volume());
+                match selected_option.0 {
+                    VOLUME_ID => {
+                        sounds.decrease_volume();
+                        save_file.set_volume(sounds.get_volume());
+                    }
+                    UNDOS_ID => {
+                        let index = undo_policy_index(save_file.get_undo_policy());
+                        let previous = (index + UNDO_POLICIES.len() - 1) % UNDO_POLICIES.len();
+                        save_file.set_undo_policy(UNDO_POLICIES[previous]);
+                    }
+                    _ => save_file.set_record_ranking(save_file.get_record_ranking().toggle()),
+                }
             }
             Input::Direction(Direction::Right) => {
                 sfx.play(sounds.sfx_move_character.clone());
-                sounds.increase_volume();
-                save_file.set_volume(sounds.get_volume());
+                match selected_option.0 {
+                    VOLUME_ID => {
+                        sounds.increase_volume();
+                        save_file.set_volume(sounds.get_volume());
+                    }
+                    UNDOS_ID => {
+                        let index = undo_policy_index(save_file.get_undo_policy());
+                        let next = (index + 1) % UNDO_POLICIES.len();
+                        save_file.set_undo_policy(UNDO_POLICIES[next]);
+                    }
+                    _ => save_file.set_record_ranking(save_file.get_record_ranking().toggle()),
+                }
             }
             Input::Action(Action::Exit) => {
                 sfx.play(sounds.sfx_push_box.clone());
@@ -41,13 +91,38 @@ pub fn handle_input(
 
 pub fn update_dynamic_text(
     sounds: Res<Sounds>,
+    save_file: Res<SaveFile>,
+    selected_option: Res<SelectedOption>,
     mut writer: TextUiWriter,
     texts: Query<(Entity, &DynamicTextData)>,
 ) {
     for (entity, data) in texts {
         *writer.text(entity, 1) = match data.id {
             VOLUME_ID => format!("<{:>4.0}%>", sounds.get_volume() * 100.0),
+            UNDOS_ID => {
+                let undo_policy = save_file.get_undo_policy().to_string();
+                format!("<{undo_policy:>8}>")
+            }
+            RANKING_ID => {
+                let record_ranking = save_file.get_record_ranking().to_string();
+                format!("<{record_ranking:>6}>")
+            }
             _ => unreachable!("The text id does not exists"),
         };
+
+        let color = if data.id == selected_option.0 {
+            crate::theme::SECONDARY
+        } else {
+            crate::theme::LIGHT
+        };
+        *writer.color(entity, 0) = TextColor(color);
+        *writer.color(entity, 1) = TextColor(color);
     }
 }
+
+fn undo_policy_index(undo_policy: UndoPolicy) -> usize {
+    UNDO_POLICIES
+        .iter()
+        .position(|policy| *policy == undo_policy)
+        .unwrap_or_default()
+}
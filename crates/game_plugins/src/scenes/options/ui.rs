@@ -4,6 +4,8 @@ use bevy_ui_bits::{Container, DynamicTextBuilder, Root, SimpleText, UiText};
 use crate::assets::prelude::*;
 
 pub const VOLUME_ID: usize = 1;
+pub const UNDOS_ID: usize = 2;
+pub const RANKING_ID: usize = 3;
 
 pub fn spawn(mut commands: Commands, fonts: Res<Fonts>) {
     let font = fonts.primary();
@@ -19,6 +21,14 @@ pub fn spawn(mut commands: Commands, fonts: Res<Fonts>) {
     let volume = DynamicTextBuilder::medium("Volume: ", font)
         .id(VOLUME_ID)
         .color(crate::theme::SECONDARY.into());
+    let undos = DynamicTextBuilder::medium("Undos: ", font)
+        .id(UNDOS_ID)
+        .color(crate::theme::SECONDARY.into());
+    let ranking = DynamicTextBuilder::medium("Records by: ", font)
+        .id(RANKING_ID)
+        .color(crate::theme::SECONDARY.into());
+    let unlimited_records = SimpleText::small("* Records made without an undo limit", font)
+        .color(crate::theme::LIGHT.into());
     let press_button = SimpleText::small("Press ESC to return to the title screen", font)
         .color(crate::theme::PRIMARY.into());
 
@@ -26,7 +36,15 @@ pub fn spawn(mut commands: Commands, fonts: Res<Fonts>) {
         root,
         children![
             (top, children![how_to_play]),
-            (center, children![volume.build()]),
+            (
+                center,
+                children![
+                    volume.build(),
+                    undos.build(),
+                    ranking.build(),
+                    unlimited_records
+                ]
+            ),
             (bottom, children![press_button])
         ],
     ));
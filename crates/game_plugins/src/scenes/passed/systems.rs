@@ -1,8 +1,7 @@
 use std::{
-    env,
-    fs::{File, create_dir_all},
-    io::Write,
-    path::PathBuf,
+    fs::{create_dir_all, write},
+    io,
+    path::Path,
 };
 
 use bevy::{
@@ -16,14 +15,14 @@ use uuid::Uuid;
 use bevy_ui_bits::DynamicTextData;
 use game_core::{
     input::{Action, Input},
-    level::LevelKind,
+    level::{LevelKind, LevelPack},
 };
 
 use crate::{
     assets::prelude::*,
     input::InputEvent,
-    level::{LevelHandles, LevelResource, LevelStateAsset},
-    save_file::SaveFile,
+    level::{LevelHandles, LevelPackFile, LevelResource},
+    save_file::{SaveFile, custom_level_asset_path, custom_level_path},
     state::{GameStateTransitionEvent, SelectionKind},
 };
 
@@ -129,29 +128,26 @@ pub fn handle_text_input(
                     let uuid = Uuid::new_v4();
                     let serialized_string = match level.kind() {
                         LevelKind::Editable(state) => {
-                            ron::ser::to_string(&LevelStateAsset::new(*state)).unwrap()
+                            let pack = LevelPack::single(&level_name, state.clone());
+                            ron::ser::to_string(&LevelPackFile(pack)).unwrap()
                         }
                         _ => panic!("Cannot get the state if the level kind is not playtest"),
                     };
-                    let levels_path = format!("levels/custom/{}.lvl", &uuid);
-                    let assets_path = format!("assets/{}", &levels_path);
+                    let uuid_string = uuid.to_string();
 
                     #[cfg(not(target_family = "wasm"))]
                     {
-                        let path = if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
-                            PathBuf::from(manifest_dir).join(assets_path)
-                        } else {
-                            PathBuf::from(assets_path)
-                        };
-
-                        let parent_path = path.parent().unwrap();
-                        create_dir_all(parent_path).unwrap();
-
-                        let mut file = File::create(path).unwrap();
-                        file.write_all(serialized_string.as_bytes()).unwrap();
+                        let path = custom_level_path(&uuid_string);
+                        if let Err(error) = write_level_file(&path, &serialized_string) {
+                            error!("Cannot save the level to {}: {error}", path.display());
+                            continue;
+                        }
                     }
 
-                    level_handles.insert_custom(uuid, asset_server.load(&levels_path));
+                    level_handles.insert_custom(
+                        uuid,
+                        asset_server.load(custom_level_asset_path(&uuid_string)),
+                    );
 
                     let lower_level_name = level_name.to_lowercase();
 
@@ -172,3 +168,10 @@ pub fn handle_text_input(
         }
     }
 }
+
+fn write_level_file(path: &Path, serialized_string: &str) -> io::Result<()> {
+    if let Some(parent_path) = path.parent() {
+        create_dir_all(parent_path)?;
+    }
+    write(path, serialized_string)
+}
@@ -1,18 +1,24 @@
 use bevy::prelude::*;
 use bevy_ui_bits::{Container, DynamicTextBuilder, Root, SimpleText, UiText};
 
-use crate::assets::prelude::*;
+use crate::{assets::prelude::*, level::LevelResource};
 
 pub const LEVEL_NAME_ID: usize = 1;
 
-pub fn spawn(mut commands: Commands, fonts: Res<Fonts>) {
+pub fn spawn(mut commands: Commands, fonts: Res<Fonts>, level: Res<LevelResource>) {
     let font = fonts.primary();
 
     let root = Root::new();
     let center = Container::size(Val::Px(600.0), Val::Px(300.0)).justify_between();
     let level_name = Container::new();
 
-    let title = SimpleText::large("Level Passed!", font).color(crate::theme::PRIMARY.into());
+    // imported levels are saved without being played
+    let title = if level.record().is_set() {
+        "Level Passed!"
+    } else {
+        "Level Imported!"
+    };
+    let title = SimpleText::large(title, font).color(crate::theme::PRIMARY.into());
     let level_name_title = SimpleText::medium("Give this level a name:", font);
     let level_name_input = DynamicTextBuilder::medium("", font)
         .id(LEVEL_NAME_ID)
@@ -8,8 +8,8 @@ impl BevyPlugin for Plugin {
             super::title::Plugin,
             super::instructions::Plugin,
             super::editor::Plugin,
-            super::limit::Plugin,
             super::passed::Plugin,
+            super::import::Plugin,
             super::options::Plugin,
             super::selection::Plugin,
             super::level::Plugin,
@@ -4,15 +4,23 @@ use bevy_ui_bits::RootMarker;
 use crate::{
     assets::prelude::*,
     input::InputEvent,
+    level::{LevelDifficulties, LevelHandles},
     save_file::SaveFile,
     state::{GameState, SelectionKind},
 };
 
-use super::systems::{handle_input, play_sfx};
+use super::systems::{
+    export_code, handle_input, play_sfx, show_selected_page, unlocked_stock_levels,
+    update_difficulty_texts,
+};
 
 #[derive(Resource)]
 pub(super) struct SelectedButton(pub usize);
 
+/// Index of the pack whose levels are shown, among the packs of the packs folder
+#[derive(Resource)]
+pub(super) struct SelectedPack(pub usize);
+
 pub struct Plugin;
 
 impl BevyPlugin for Plugin {
@@ -20,17 +28,30 @@ impl BevyPlugin for Plugin {
         for state in [
             GameState::Selection(SelectionKind::Stock),
             GameState::Selection(SelectionKind::Custom),
+            GameState::Selection(SelectionKind::Pack),
         ] {
             app.insert_resource(SelectedButton(0))
+                .insert_resource(SelectedPack(0))
                 .add_systems(
                     OnEnter(state),
-                    (initial_selected_button, super::ui::spawn).chain(),
+                    (
+                        initial_selected_button,
+                        super::ui::spawn,
+                        update_difficulty_texts,
+                        show_selected_page,
+                    )
+                        .chain(),
                 )
                 .add_systems(
                     Update,
                     (
                         handle_input.run_if(on_event::<InputEvent>),
                         play_sfx.run_if(on_event::<InputEvent>),
+                        export_code.run_if(on_event::<InputEvent>),
+                        update_difficulty_texts.run_if(resource_changed::<LevelDifficulties>),
+                        show_selected_page
+                            .after(handle_input)
+                            .run_if(resource_changed::<SelectedButton>),
                     )
                         .run_if(in_state(state)),
                 )
@@ -42,18 +63,24 @@ impl BevyPlugin for Plugin {
 pub fn initial_selected_button(
     game_state: Res<State<GameState>>,
     save_file: Res<SaveFile>,
+    level_handles: Res<LevelHandles>,
     mut selected_button: ResMut<SelectedButton>,
 ) {
     #[cfg(not(target_family = "wasm"))]
     {
         match game_state.get_selection_kind() {
-            SelectionKind::Stock => selected_button.0 = save_file.unlocked_levels() - 1,
-            SelectionKind::Custom => selected_button.0 = save_file.number_custom_levels() - 1,
+            SelectionKind::Stock => {
+                selected_button.0 = unlocked_stock_levels(&save_file, &level_handles) - 1
+            }
+            SelectionKind::Custom => {
+                selected_button.0 = save_file.number_custom_levels().saturating_sub(1)
+            }
+            SelectionKind::Pack => selected_button.0 = 0,
         }
     }
 
     #[cfg(target_family = "wasm")]
     {
-        selected_button.0 = save_file.unlocked_levels() - 1;
+        selected_button.0 = unlocked_stock_levels(&save_file, &level_handles) - 1;
     }
 }
@@ -1,22 +1,70 @@
-use std::{env, fs::remove_file, path::PathBuf};
+use std::fs::remove_file;
 
 use bevy::prelude::*;
 use bevy_kira_audio::{AudioChannel, AudioControl};
 
-use bevy_ui_bits::UiButtonData;
+use bevy_ui_bits::{DynamicTextData, UiButtonData};
 use game_core::{
     input::{Action, Direction, Input},
     level::LevelKind,
 };
+use uuid::Uuid;
 
 use crate::{
     assets::prelude::*,
+    clipboard::Clipboard,
     input::InputEvent,
-    level::LevelInsertionEvent,
-    save_file::SaveFile,
+    level::{
+        LevelDifficulties, LevelHandles, LevelInsertionEvent, LevelPackAsset, LevelStateAsset,
+    },
+    save_file::{SaveFile, custom_level_path},
     state::{GameState, GameStateTransitionEvent, SelectionKind},
 };
 
+use super::{
+    plugin::{SelectedButton, SelectedPack},
+    ui::{EXPORTED_CODE_ID, PAGE_ID},
+};
+
+/// Buttons shown at once, the grid has four rows of four
+const PAGE_SIZE: usize = 16;
+
+/// Selection that follows the given one, going through the stock levels, the custom
+/// levels and then every pack, before going back to the stock levels
+pub(super) fn next_selection(
+    kind: SelectionKind,
+    selected_pack: usize,
+    number_packs: usize,
+) -> (SelectionKind, usize) {
+    match kind {
+        SelectionKind::Stock => (SelectionKind::Custom, 0),
+        SelectionKind::Custom if number_packs > 0 => (SelectionKind::Pack, 0),
+        SelectionKind::Pack if selected_pack + 1 < number_packs => {
+            (SelectionKind::Pack, selected_pack + 1)
+        }
+        SelectionKind::Custom | SelectionKind::Pack => (SelectionKind::Stock, 0),
+    }
+}
+
+/// Unlocked stock levels that are still in the stock pack, in case it lost levels
+/// since they were unlocked
+pub(super) fn unlocked_stock_levels(save_file: &SaveFile, level_handles: &LevelHandles) -> usize {
+    save_file
+        .unlocked_levels()
+        .min(level_handles.number_stock())
+}
+
+/// Key of the selected pack along with its levels
+pub(super) fn get_selected_pack<'a>(
+    selected_pack: &SelectedPack,
+    level_handles: &'a LevelHandles,
+    level_packs_assets: &'a Assets<LevelPackAsset>,
+) -> Option<(&'a str, &'a LevelPackAsset)> {
+    level_handles
+        .get_pack(selected_pack.0)
+        .and_then(|(key, handle)| Some((key.as_str(), level_packs_assets.get(handle)?)))
+}
+
 #[allow(clippy::too_many_lines)]
 pub fn handle_input(
     mut level_insertion_event_writer: EventWriter<LevelInsertionEvent>,
@@ -25,9 +73,13 @@ pub fn handle_input(
     mut input_event_reader: EventReader<InputEvent>,
     mut save_file: ResMut<SaveFile>,
     game_state: Res<State<GameState>>,
-    mut selected_button: ResMut<super::plugin::SelectedButton>,
+    mut selected_button: ResMut<SelectedButton>,
+    mut selected_pack: ResMut<SelectedPack>,
+    level_handles: Res<LevelHandles>,
+    level_packs_assets: Res<Assets<LevelPackAsset>>,
 ) {
-    let is_stock = game_state.get_selection_kind().is_stock();
+    let kind = *game_state.get_selection_kind();
+    let pack = get_selected_pack(&selected_pack, &level_handles, &level_packs_assets);
 
     for input_event in input_event_reader.read() {
         match **input_event {
@@ -39,11 +91,14 @@ pub fn handle_input(
                     Direction::Right => selected_button.0 + 1,
                 };
 
-                let max_value = if is_stock {
-                    save_file.unlocked_levels()
-                } else {
-                    save_file.number_custom_levels()
+                let max_value = match kind {
+                    SelectionKind::Stock => unlocked_stock_levels(&save_file, &level_handles),
+                    SelectionKind::Custom => save_file.number_custom_levels(),
+                    SelectionKind::Pack => pack.map_or(0, |(_, pack)| pack.len()),
                 };
+                if max_value == 0 {
+                    continue;
+                }
 
                 selected_button.0 = if index < max_value {
                     index
@@ -62,35 +117,46 @@ pub fn handle_input(
             Input::Action(Action::Select) => {
                 for (button, _) in &mut query {
                     if button.id == selected_button.0 {
-                        let kind = if is_stock {
-                            LevelKind::Stock(button.id)
-                        } else {
-                            LevelKind::Custom(
+                        let level_kind = match kind {
+                            SelectionKind::Stock => LevelKind::Stock(button.id),
+                            SelectionKind::Custom => LevelKind::Custom(
                                 button
                                     .payload
                                     .clone()
                                     .expect("The button payload was empty"),
-                            )
+                            ),
+                            SelectionKind::Pack => {
+                                let Some((key, _)) = pack else {
+                                    continue;
+                                };
+                                LevelKind::Pack(key.to_string(), button.id)
+                            }
                         };
 
-                        level_insertion_event_writer.write(LevelInsertionEvent::new(kind));
+                        level_insertion_event_writer.write(LevelInsertionEvent::new(level_kind));
                     }
                 }
             }
+            Input::Action(Action::Mode) => {
+                save_file.set_play_mode(save_file.get_play_mode().toggle());
+                save_file.save();
+                scene_transition_event_writer.write(GameStateTransitionEvent::selection(kind));
+            }
+            Input::Action(Action::Import) if kind == SelectionKind::Custom => {
+                scene_transition_event_writer.write(GameStateTransitionEvent::import());
+            }
             Input::Action(Action::Toggle) => {
                 #[cfg(not(target_family = "wasm"))]
                 {
-                    scene_transition_event_writer.write(GameStateTransitionEvent::selection(
-                        if is_stock {
-                            SelectionKind::Custom
-                        } else {
-                            SelectionKind::Stock
-                        },
-                    ));
+                    let (next_kind, next_pack) =
+                        next_selection(kind, selected_pack.0, level_handles.number_packs());
+                    selected_pack.0 = next_pack;
+                    scene_transition_event_writer
+                        .write(GameStateTransitionEvent::selection(next_kind));
                 }
             }
 
-            Input::Action(Action::Delete) => {
+            Input::Action(Action::Delete) if kind == SelectionKind::Custom => {
                 for (button, _) in &mut query {
                     if button.id == selected_button.0 {
                         let payload = button
@@ -102,14 +168,8 @@ pub fn handle_input(
                         save_file.save();
                         #[cfg(not(target_family = "wasm"))]
                         {
-                            let levels_path = format!("levels/custom/{}.lvl", parsed_payload[1]);
-                            let assets_path = format!("assets/{}", &levels_path);
-                            let path = if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
-                                PathBuf::from(manifest_dir).join(assets_path)
-                            } else {
-                                PathBuf::from(assets_path)
-                            };
-                            remove_file(path).expect("File cannot be removed");
+                            remove_file(custom_level_path(parsed_payload[1]))
+                                .expect("File cannot be removed");
                         }
                         scene_transition_event_writer
                             .write(GameStateTransitionEvent::selection(SelectionKind::Custom));
@@ -124,6 +184,133 @@ pub fn handle_input(
     }
 }
 
+/// Shows the code of the selected custom level and copies it, it is also logged in case
+/// the clipboard cannot be reached
+pub fn export_code(
+    game_state: Res<State<GameState>>,
+    save_file: Res<SaveFile>,
+    selected_button: Res<SelectedButton>,
+    level_handles: Res<LevelHandles>,
+    level_states_assets: Res<Assets<LevelStateAsset>>,
+    mut input_event_reader: EventReader<InputEvent>,
+    mut clipboard: NonSendMut<Clipboard>,
+    mut writer: TextUiWriter,
+    texts: Query<(Entity, &DynamicTextData)>,
+) {
+    if *game_state.get_selection_kind() != SelectionKind::Custom
+        || !input_event_reader
+            .read()
+            .any(|input_event| matches!(**input_event, Input::Action(Action::Export)))
+    {
+        return;
+    }
+
+    let Some((_, (key, _))) = save_file
+        .ordered_custom_records()
+        .find(|(index, _)| *index == selected_button.0)
+    else {
+        return;
+    };
+    let mut split_key = key.split('$');
+    let name = split_key.next().unwrap_or_default();
+    let Some(state) = split_key
+        .next()
+        .and_then(|uuid| Uuid::parse_str(uuid).ok())
+        .and_then(|uuid| level_handles.get_custom(&uuid))
+        .and_then(|handle| level_states_assets.get(handle))
+    else {
+        return;
+    };
+
+    let code = state.to_code();
+    info!("Code of the level {name}: {code}");
+    let copied = clipboard.set_text(&code);
+    for (entity, data) in texts {
+        if data.id == EXPORTED_CODE_ID {
+            *writer.text(entity, 1) = if copied {
+                format!("{code} (copied)")
+            } else {
+                code.clone()
+            };
+        }
+    }
+}
+
+pub fn update_difficulty_texts(
+    game_state: Res<State<GameState>>,
+    save_file: Res<SaveFile>,
+    selected_pack: Res<SelectedPack>,
+    level_handles: Res<LevelHandles>,
+    difficulties: Res<LevelDifficulties>,
+    mut writer: TextUiWriter,
+    texts: Query<(Entity, &DynamicTextData)>,
+) {
+    let kind = *game_state.get_selection_kind();
+    let pack_key = level_handles
+        .get_pack(selected_pack.0)
+        .map(|(key, _)| key.as_str());
+    let custom_keys: Vec<&String> = save_file
+        .ordered_custom_records()
+        .map(|(_, (key, _))| key)
+        .collect();
+
+    for (entity, data) in texts {
+        if data.id == EXPORTED_CODE_ID || data.id == PAGE_ID {
+            continue;
+        }
+
+        let handle = match kind {
+            SelectionKind::Stock => level_handles.get_stock(data.id),
+            SelectionKind::Custom => custom_keys
+                .get(data.id)
+                .and_then(|key| key.split('$').nth(1))
+                .and_then(|uuid| Uuid::parse_str(uuid).ok())
+                .and_then(|uuid| level_handles.get_custom(&uuid)),
+            SelectionKind::Pack => {
+                pack_key.and_then(|key| level_handles.get_pack_level(key, data.id))
+            }
+        };
+
+        *writer.text(entity, 1) = handle
+            .and_then(|handle| difficulties.get(handle))
+            .map_or(String::new(), |label| label.to_string());
+    }
+}
+
+/// Hides the buttons that are not in the page of the selected button, so any number
+/// of levels fits in the grid
+pub fn show_selected_page(
+    selected_button: Res<SelectedButton>,
+    buttons: Query<(&UiButtonData, &ChildOf)>,
+    mut housings: Query<&mut Node>,
+    mut writer: TextUiWriter,
+    texts: Query<(Entity, &DynamicTextData)>,
+) {
+    let page = selected_button.0 / PAGE_SIZE;
+    let mut total_pages = 0;
+
+    for (button, child_of) in &buttons {
+        total_pages = total_pages.max(button.id / PAGE_SIZE + 1);
+        if let Ok(mut housing) = housings.get_mut(child_of.parent()) {
+            housing.display = if button.id / PAGE_SIZE == page {
+                Display::Flex
+            } else {
+                Display::None
+            };
+        }
+    }
+
+    for (entity, data) in texts {
+        if data.id == PAGE_ID {
+            *writer.text(entity, 1) = if total_pages > 1 {
+                format!("Page {} of {total_pages}", page + 1)
+            } else {
+                String::new()
+            };
+        }
+    }
+}
+
 pub fn play_sfx(
     mut input_event_reader: EventReader<InputEvent>,
     sounds: Res<Sounds>,
@@ -134,13 +321,13 @@ pub fn play_sfx(
             Input::Direction(_) => {
                 sfx.play(sounds.sfx_move_character.clone());
             }
-            Input::Action(Action::Exit | Action::Delete) => {
+            Input::Action(Action::Exit | Action::Delete | Action::Import) => {
                 sfx.play(sounds.sfx_push_box.clone());
             }
-            Input::Action(Action::Toggle) => {
+            Input::Action(Action::Toggle | Action::Mode) => {
                 sfx.play(sounds.sfx_toggle_volume.clone());
             }
-            Input::Action(Action::Select) => {
+            Input::Action(Action::Select | Action::Export) => {
                 sfx.play(sounds.sfx_set_zone.clone());
             }
             Input::Action(_) => (),
@@ -1,15 +1,41 @@
 use bevy::{ecs::spawn::SpawnIter, prelude::*};
-use bevy_ui_bits::{Container, EmbossedText, Root, SimpleText, UiButton, UiText};
+use bevy_ui_bits::{
+    Container, DynamicTextBuilder, EmbossedText, Root, SimpleText, UiButton, UiText,
+};
+use game_core::level::LevelKind;
 
-use crate::{assets::prelude::*, save_file::SaveFile, state::GameState};
+use crate::{
+    assets::prelude::*,
+    level::{LevelHandles, LevelPackAsset},
+    save_file::SaveFile,
+    state::{GameState, SelectionKind},
+};
+
+use super::{
+    plugin::SelectedPack,
+    systems::{get_selected_pack, next_selection, unlocked_stock_levels},
+};
+
+/// Ids past the level buttons
+pub const EXPORTED_CODE_ID: usize = usize::MAX;
+pub const PAGE_ID: usize = usize::MAX - 1;
 
 fn spawn_stock_buttons(
     save_file: &SaveFile,
+    level_handles: &LevelHandles,
     font: &Handle<Font>,
-) -> Vec<(Container, UiButton, EmbossedText, SimpleText)> {
+) -> Vec<(
+    Container,
+    UiButton,
+    EmbossedText,
+    SimpleText,
+    DynamicTextBuilder,
+)> {
     let mut buttons = vec![];
 
-    for (index, record) in save_file.enumerated_stock_records() {
+    let unlocked_levels = unlocked_stock_levels(save_file, level_handles);
+    for (index, _) in save_file.enumerated_stock_records().take(unlocked_levels) {
+        let record = save_file.get_record(&LevelKind::Stock(index), save_file.get_play_mode());
         let housing = Container::size(Val::Percent(25.0), Val::Percent(25.0));
         let mut button = UiButton::square().id(index);
         let button_text = EmbossedText::medium(&format!("{}", index + 1), font);
@@ -19,11 +45,15 @@ fn spawn_stock_buttons(
             SimpleText::small("New Level!\n ", font).color(crate::theme::SECONDARY.into())
         };
 
-        if index == save_file.unlocked_levels() - 1 {
+        if index == unlocked_levels - 1 {
             button = button.background_color(crate::theme::PRIMARY_DARK);
         }
 
-        buttons.push((housing, button, button_text, record_new_level));
+        let difficulty = DynamicTextBuilder::small("", font)
+            .id(index)
+            .color(crate::theme::PRIMARY.into());
+
+        buttons.push((housing, button, button_text, record_new_level, difficulty));
     }
 
     buttons
@@ -32,10 +62,18 @@ fn spawn_stock_buttons(
 fn spawn_custom_buttons(
     save_file: &SaveFile,
     font: &Handle<Font>,
-) -> Vec<(Container, UiButton, EmbossedText, SimpleText)> {
+) -> Vec<(
+    Container,
+    UiButton,
+    EmbossedText,
+    SimpleText,
+    DynamicTextBuilder,
+)> {
     let mut buttons = vec![];
 
-    for (index, (key, record)) in save_file.ordered_custom_records() {
+    for (index, (key, _)) in save_file.ordered_custom_records() {
+        let record =
+            save_file.get_record(&LevelKind::Custom(key.clone()), save_file.get_play_mode());
         let housing = Container::size(Val::Percent(25.0), Val::Percent(25.0));
         let split_key: Vec<&str> = key.split('$').collect();
         let mut button = UiButton::rectangle().id(index).payload(key);
@@ -50,7 +88,53 @@ fn spawn_custom_buttons(
             button = button.background_color(crate::theme::PRIMARY_DARK);
         }
 
-        buttons.push((housing, button, button_text, record_new_level));
+        let difficulty = DynamicTextBuilder::small("", font)
+            .id(index)
+            .color(crate::theme::PRIMARY.into());
+
+        buttons.push((housing, button, button_text, record_new_level, difficulty));
+    }
+
+    buttons
+}
+
+fn spawn_pack_buttons(
+    save_file: &SaveFile,
+    key: &str,
+    pack: &LevelPackAsset,
+    font: &Handle<Font>,
+) -> Vec<(
+    Container,
+    UiButton,
+    EmbossedText,
+    SimpleText,
+    DynamicTextBuilder,
+)> {
+    let mut buttons = vec![];
+
+    for index in 0..pack.len() {
+        let record = save_file.get_record(
+            &LevelKind::Pack(key.to_string(), index),
+            save_file.get_play_mode(),
+        );
+        let housing = Container::size(Val::Percent(25.0), Val::Percent(25.0));
+        let mut button = UiButton::square().id(index);
+        let button_text = EmbossedText::medium(&format!("{}", index + 1), font);
+        let record_new_level = if record.is_set() {
+            SimpleText::small(&format!("Record: {}", record.moves_in_time('\n')), font)
+        } else {
+            SimpleText::small("New Level!\n ", font).color(crate::theme::SECONDARY.into())
+        };
+
+        if index == 0 {
+            button = button.background_color(crate::theme::PRIMARY_DARK);
+        }
+
+        let difficulty = DynamicTextBuilder::small("", font)
+            .id(index)
+            .color(crate::theme::PRIMARY.into());
+
+        buttons.push((housing, button, button_text, record_new_level, difficulty));
     }
 
     buttons
@@ -61,6 +145,9 @@ pub fn spawn(
     game_state: Res<State<GameState>>,
     fonts: Res<Fonts>,
     save_file: Res<SaveFile>,
+    selected_pack: Res<SelectedPack>,
+    level_handles: Res<LevelHandles>,
+    level_packs_assets: Res<Assets<LevelPackAsset>>,
 ) {
     let font = fonts.primary();
 
@@ -75,27 +162,66 @@ pub fn spawn(
         .items_start()
         .content_start();
 
-    let kind = game_state.get_selection_kind();
-    let title = SimpleText::medium(&format!("Select a {} Level", kind.to_str()), font)
+    let kind = *game_state.get_selection_kind();
+    let pack = get_selected_pack(&selected_pack, &level_handles, &level_packs_assets);
+    let title = if save_file.get_play_mode().is_pull() {
+        format!("Select a {} Level to Pull", kind.to_str())
+    } else {
+        format!("Select a {} Level", kind.to_str())
+    };
+    let title = SimpleText::medium(&title, font).color(crate::theme::PRIMARY.into());
+    let credits = match pack {
+        Some((_, pack)) if kind == SelectionKind::Pack => {
+            let mut credits = pack.title().to_string();
+            if !pack.author().is_empty() {
+                credits.push_str(&format!(" by {}", pack.author()));
+            }
+            if !pack.license().is_empty() {
+                credits.push_str(&format!(" ({})", pack.license()));
+            }
+            if !pack.description().is_empty() {
+                credits.push_str(&format!("\n{}", pack.description()));
+            }
+            credits
+        }
+        _ => String::new(),
+    };
+    let credits = SimpleText::small(&credits, font).color(crate::theme::SECONDARY.into());
+    let page = DynamicTextBuilder::small("", font)
+        .id(PAGE_ID)
         .color(crate::theme::PRIMARY.into());
+    let mode = SimpleText::small(
+        &format!(
+            "(M) - Switch to {} mode",
+            save_file.get_play_mode().toggle()
+        ),
+        font,
+    )
+    .color(crate::theme::PRIMARY.into());
 
     #[cfg(target_family = "wasm")]
     {
         commands.spawn((
             root,
             children![
-                (top, children![title]),
+                (top, children![title, credits, mode, page.build()]),
                 (
                     middle,
                     Children::spawn(SpawnIter(
-                        spawn_stock_buttons(&save_file, font).into_iter().map(
-                            |(housing, button, button_text, record_new_level)| {
-                                (
-                                    housing,
-                                    children![(button, children![button_text]), record_new_level],
-                                )
-                            }
-                        ),
+                        spawn_stock_buttons(&save_file, &level_handles, font)
+                            .into_iter()
+                            .map(
+                                |(housing, button, button_text, record_new_level, difficulty)| {
+                                    (
+                                        housing,
+                                        children![
+                                            (button, children![button_text]),
+                                            record_new_level,
+                                            difficulty.build()
+                                        ],
+                                    )
+                                }
+                            ),
                     ))
                 )
             ],
@@ -107,38 +233,74 @@ pub fn spawn(
         let bottom = Container::width(Val::Percent(100.0))
             .row()
             .justify_between();
-        let enter = SimpleText::small(
-            &format!("(ENTER) - Switch to {} levels", kind.toggle().to_str()),
+        let (next_kind, next_pack) =
+            next_selection(kind, selected_pack.0, level_handles.number_packs());
+        let next_name = match next_kind {
+            SelectionKind::Pack => level_handles
+                .get_pack(next_pack)
+                .and_then(|(_, handle)| level_packs_assets.get(handle))
+                .map_or(next_kind.to_str(), |pack| pack.title()),
+            _ => next_kind.to_str(),
+        };
+        let enter = SimpleText::small(&format!("(ENTER) - Switch to {next_name} levels"), font)
+            .color(crate::theme::PRIMARY.into());
+        let delete = SimpleText::small(
+            if kind == SelectionKind::Custom {
+                "(DELETE) - Remove a custom level"
+            } else {
+                ""
+            },
             font,
         )
         .color(crate::theme::PRIMARY.into());
-        let delete = SimpleText::small("(DELETE) - Remove a custom level", font)
-            .color(crate::theme::PRIMARY.into());
+        let share = SimpleText::small(
+            if kind == SelectionKind::Custom {
+                "(E) - Export code / (I) - Import code"
+            } else {
+                ""
+            },
+            font,
+        )
+        .color(crate::theme::PRIMARY.into());
+        let exported_code = DynamicTextBuilder::small("", font)
+            .id(EXPORTED_CODE_ID)
+            .color(crate::theme::SECONDARY.into());
 
         commands.spawn((
             root,
             children![
-                (top, children![title]),
+                (
+                    top,
+                    children![title, credits, mode, page.build(), exported_code.build()]
+                ),
                 (
                     middle,
                     Children::spawn(SpawnIter(
-                        (if kind.is_stock() {
-                            spawn_stock_buttons(&save_file, font)
-                        } else {
-                            spawn_custom_buttons(&save_file, font)
+                        (match (kind, pack) {
+                            (SelectionKind::Stock, _) =>
+                                spawn_stock_buttons(&save_file, &level_handles, font),
+                            (SelectionKind::Custom, _) => spawn_custom_buttons(&save_file, font),
+                            (SelectionKind::Pack, Some((key, pack))) => {
+                                spawn_pack_buttons(&save_file, key, pack, font)
+                            }
+                            (SelectionKind::Pack, None) => vec![],
                         })
                         .into_iter()
                         .map(
-                            |(housing, button, button_text, record_new_level)| {
+                            |(housing, button, button_text, record_new_level, difficulty)| {
                                 (
                                     housing,
-                                    children![(button, children![button_text]), record_new_level],
+                                    children![
+                                        (button, children![button_text]),
+                                        record_new_level,
+                                        difficulty.build()
+                                    ],
                                 )
                             }
                         ),
                     ))
                 ),
-                (bottom, children![enter, delete])
+                (bottom, children![enter, share, delete])
             ],
         ));
     }
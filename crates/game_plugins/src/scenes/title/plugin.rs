@@ -5,11 +5,12 @@ use crate::{
     assets::prelude::*,
     character::{Character, CharacterAnimation},
     input::InputEvent,
+    level::LevelGeneration,
     state::GameState,
 };
 
 use super::{
-    systems::{handle_input, play_sfx},
+    systems::{handle_input, play_sfx, update_generation_status},
     ui,
 };
 
@@ -34,6 +35,7 @@ impl BevyPlugin for Plugin {
                     CharacterAnimation::update_blinking_character_animation,
                     handle_input.run_if(on_event::<InputEvent>),
                     play_sfx.run_if(on_event::<InputEvent>),
+                    update_generation_status.run_if(resource_changed::<LevelGeneration>),
                 )
                     .run_if(in_state(GameState::Title)),
             )
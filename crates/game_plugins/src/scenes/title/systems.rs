@@ -1,24 +1,29 @@
 use bevy::{app::AppExit, prelude::*};
 use bevy_kira_audio::{AudioChannel, AudioControl};
-use bevy_ui_bits::UiButtonData;
+use bevy_ui_bits::{DynamicTextData, UiButtonData};
 use game_core::input::{Action, Direction, Input};
 
 use crate::{
     assets::prelude::*,
     input::InputEvent,
+    level::{GenerationStatus, LevelGeneration, LevelInsertionEvent},
     scenes::title::plugin::SelectedButton,
     state::{GameStateTransitionEvent, SelectionKind},
 };
 
-use super::ui::{EDITOR_ID, INSTRUCTIONS_ID, OPTIONS_ID, PLAY_ID, QUIT_ID};
+use super::ui::{
+    EDITOR_ID, GENERATE_ID, GENERATION_STATUS_ID, INSTRUCTIONS_ID, OPTIONS_ID, PLAY_ID, QUIT_ID,
+};
 
 #[allow(clippy::too_many_lines)]
 pub fn handle_input(
     mut query: Query<(&UiButtonData, &mut BackgroundColor)>,
     mut input_event_reader: EventReader<InputEvent>,
     mut game_state_event_writer: EventWriter<GameStateTransitionEvent>,
+    mut level_insertion_event_writer: EventWriter<LevelInsertionEvent>,
     mut exit: EventWriter<AppExit>,
     mut selected_button: ResMut<SelectedButton>,
+    mut generation: ResMut<LevelGeneration>,
 ) {
     for input_event in input_event_reader.read() {
         match **input_event {
@@ -31,13 +36,20 @@ pub fn handle_input(
                             PLAY_ID => {
                                 if up {
                                     QUIT_ID
+                                } else {
+                                    GENERATE_ID
+                                }
+                            }
+                            GENERATE_ID => {
+                                if up {
+                                    PLAY_ID
                                 } else {
                                     INSTRUCTIONS_ID
                                 }
                             }
                             INSTRUCTIONS_ID => {
                                 if up {
-                                    PLAY_ID
+                                    GENERATE_ID
                                 } else {
                                     EDITOR_ID
                                 }
@@ -73,13 +85,20 @@ pub fn handle_input(
                             PLAY_ID => {
                                 if up {
                                     OPTIONS_ID
+                                } else {
+                                    GENERATE_ID
+                                }
+                            }
+                            GENERATE_ID => {
+                                if up {
+                                    PLAY_ID
                                 } else {
                                     INSTRUCTIONS_ID
                                 }
                             }
                             INSTRUCTIONS_ID => {
                                 if up {
-                                    PLAY_ID
+                                    GENERATE_ID
                                 } else {
                                     OPTIONS_ID
                                 }
@@ -109,6 +128,9 @@ pub fn handle_input(
                     game_state_event_writer
                         .write(GameStateTransitionEvent::selection(SelectionKind::Stock));
                 }
+                GENERATE_ID => {
+                    level_insertion_event_writer.write(LevelInsertionEvent::generated());
+                }
                 INSTRUCTIONS_ID => {
                     game_state_event_writer.write(GameStateTransitionEvent::instructions());
                 }
@@ -127,6 +149,11 @@ pub fn handle_input(
                 _ => unreachable!("The button id was not declared"),
             },
             Input::Action(Action::Exit) => {
+                // exiting while a level is generated only stops the generation
+                if generation.status() == GenerationStatus::Generating {
+                    generation.cancel();
+                    continue;
+                }
                 #[cfg(not(target_family = "wasm"))]
                 {
                     exit.write(AppExit::Success);
@@ -137,6 +164,27 @@ pub fn handle_input(
     }
 }
 
+pub fn update_generation_status(
+    generation: Res<LevelGeneration>,
+    mut writer: TextUiWriter,
+    texts: Query<(Entity, &DynamicTextData)>,
+) {
+    for (entity, data) in texts {
+        *writer.text(entity, 1) = match data.id {
+            GENERATION_STATUS_ID => match generation.status() {
+                GenerationStatus::Idle => String::new(),
+                GenerationStatus::Generating => {
+                    "\n> Generating a level... (ESC to cancel) <".to_string()
+                }
+                GenerationStatus::Failed => {
+                    "\n> The level could not be generated, try again <".to_string()
+                }
+            },
+            _ => unreachable!("The text id does not exists"),
+        };
+    }
+}
+
 pub fn play_sfx(
     mut input_event_reader: EventReader<InputEvent>,
     sounds: Res<Sounds>,
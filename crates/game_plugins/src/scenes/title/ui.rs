@@ -1,19 +1,25 @@
 use bevy::{prelude::*, text::LineHeight};
-use bevy_ui_bits::{Container, EmbossedText, Root, SimpleText, UiButton, UiText};
+use bevy_ui_bits::{
+    Container, DynamicTextBuilder, EmbossedText, Root, SimpleText, UiButton, UiText,
+};
 
-use crate::assets::prelude::*;
+use crate::{assets::prelude::*, save_file::SaveFile};
 
 pub const PLAY_ID: usize = 0;
 pub const INSTRUCTIONS_ID: usize = 1;
 pub const EDITOR_ID: usize = 2;
 pub const OPTIONS_ID: usize = 3;
 pub const QUIT_ID: usize = 4;
+pub const GENERATE_ID: usize = 5;
+
+pub const GENERATION_STATUS_ID: usize = 0;
 
 #[allow(clippy::too_many_lines)]
 pub fn spawn(
     mut commands: Commands,
     fonts: Res<Fonts>,
     selected_button: Res<super::plugin::SelectedButton>,
+    save_file: Res<SaveFile>,
 ) {
     let font = fonts.primary();
 
@@ -21,21 +27,21 @@ pub fn spawn(
     let center: Container;
     #[cfg(not(target_family = "wasm"))]
     {
-        center = Container::height(Val::Px(700.0)).justify_between();
+        center = Container::height(Val::Px(720.0)).justify_between();
     }
     #[cfg(target_family = "wasm")]
     {
-        center = Container::height(Val::Px(600.0)).justify_between();
+        center = Container::height(Val::Px(680.0)).justify_between();
     }
     let top = Container::new();
     let bottom: Container;
     #[cfg(not(target_family = "wasm"))]
     {
-        bottom = Container::height(Val::Px(380.0)).justify_between();
+        bottom = Container::height(Val::Px(450.0)).justify_between();
     }
     #[cfg(target_family = "wasm")]
     {
-        bottom = Container::height(Val::Px(280.0)).justify_between();
+        bottom = Container::height(Val::Px(350.0)).justify_between();
     }
     let actions = Container::new();
     let footer = Container::new();
@@ -44,9 +50,23 @@ pub fn spawn(
         .color(crate::theme::PRIMARY.into())
         .line_height(LineHeight::RelativeToFont(1.0));
     let notice = SimpleText::small("By @septum\nand @weymanator", font);
+    // empty unless the progress cannot be saved
+    let save_error = SimpleText::small(
+        &save_file
+            .get_error()
+            .map_or(String::new(), |error| format!("\n> {error} <")),
+        font,
+    )
+    .color(crate::theme::PRIMARY.into());
+    // empty unless a level is being generated or could not be
+    let generation_status = DynamicTextBuilder::small("", font)
+        .id(GENERATION_STATUS_ID)
+        .color(crate::theme::PRIMARY.into());
 
     let mut play = UiButton::rectangle().id(PLAY_ID);
     let play_text = EmbossedText::medium("Play", font);
+    let mut generate = UiButton::rectangle().id(GENERATE_ID);
+    let generate_text = EmbossedText::medium("Generate", font);
     let mut instructions = UiButton::rectangle().id(INSTRUCTIONS_ID);
     let instructions_text = EmbossedText::medium("Instructions", font);
     let mut options = UiButton::rectangle().id(OPTIONS_ID);
@@ -56,6 +76,9 @@ pub fn spawn(
         PLAY_ID => {
             play = play.background_color(crate::theme::PRIMARY_DARK);
         }
+        GENERATE_ID => {
+            generate = generate.background_color(crate::theme::PRIMARY_DARK);
+        }
         INSTRUCTIONS_ID => {
             instructions = instructions.background_color(crate::theme::PRIMARY_DARK);
         }
@@ -86,11 +109,20 @@ pub fn spawn(
                                 actions,
                                 children![
                                     (play, children![play_text]),
+                                    (generate, children![generate_text]),
                                     (instructions, children![instructions_text]),
                                     (options, children![options_text]),
                                 ]
                             ),
-                            (footer, children![notice, editor_available])
+                            (
+                                footer,
+                                children![
+                                    notice,
+                                    editor_available,
+                                    save_error,
+                                    generation_status.build()
+                                ]
+                            )
                         ]
                     )
                 ]
@@ -128,13 +160,17 @@ pub fn spawn(
                                 actions,
                                 children![
                                     (play, children![play_text]),
+                                    (generate, children![generate_text]),
                                     (instructions, children![instructions_text]),
                                     (editor, children![editor_text]),
                                     (options, children![options_text]),
                                     (quit, children![quit_text]),
                                 ]
                             ),
-                            (footer, children![notice])
+                            (
+                                footer,
+                                children![notice, save_error, generation_status.build()]
+                            )
                         ]
                     )
                 ]
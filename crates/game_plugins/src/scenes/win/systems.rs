@@ -7,14 +7,18 @@ use game_core::{
 
 use crate::{
     input::InputEvent,
-    level::{LevelInsertionEvent, LevelResource},
+    level::{LevelHandles, LevelInsertionEvent, LevelPackAsset, LevelResource},
     save_file::SaveFile,
     state::{GameStateTransitionEvent, SelectionKind},
 };
 
-pub fn save(mut save_file: ResMut<SaveFile>, level: Res<LevelResource>) {
+pub fn save(
+    mut save_file: ResMut<SaveFile>,
+    level: Res<LevelResource>,
+    level_handles: Res<LevelHandles>,
+) {
     save_file.set_new_record(&level);
-    save_file.unlock_new_level(&level);
+    save_file.unlock_new_level(&level, level_handles.number_stock());
     save_file.save();
 }
 
@@ -23,12 +27,18 @@ pub fn handle_input(
     mut game_state_event_writer: EventWriter<GameStateTransitionEvent>,
     mut input_event_reader: EventReader<InputEvent>,
     level: Res<LevelResource>,
+    save_file: Res<SaveFile>,
+    level_handles: Res<LevelHandles>,
+    level_packs_assets: Res<Assets<LevelPackAsset>>,
 ) {
     for input_event in input_event_reader.read() {
         match **input_event {
             Input::Action(Action::Select) => match level.kind() {
                 LevelKind::Stock(index) => {
-                    if level.is_last() {
+                    // pulling does not unlock levels, so the next one may still be locked
+                    if level.is_last(level_handles.number_stock())
+                        || *index + 1 >= save_file.unlocked_levels()
+                    {
                         game_state_event_writer
                             .write(GameStateTransitionEvent::selection(SelectionKind::Stock));
                     } else {
@@ -40,6 +50,23 @@ pub fn handle_input(
                     game_state_event_writer
                         .write(GameStateTransitionEvent::selection(SelectionKind::Custom));
                 }
+                LevelKind::Pack(key, index) => {
+                    let pack_len = level_handles
+                        .get_pack_by_key(key)
+                        .and_then(|handle| level_packs_assets.get(handle))
+                        .map_or(0, |pack| pack.len());
+                    if level.is_last(pack_len) {
+                        game_state_event_writer
+                            .write(GameStateTransitionEvent::selection(SelectionKind::Pack));
+                    } else {
+                        level_instertion_event_writer.write(LevelInsertionEvent::new(
+                            LevelKind::Pack(key.clone(), index + 1),
+                        ));
+                    }
+                }
+                LevelKind::Generated(_) => {
+                    level_instertion_event_writer.write(LevelInsertionEvent::generated());
+                }
                 LevelKind::Editable(_) => {
                     unreachable!("An editable level cannot be won");
                 }
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use bevy_ui_bits::{Container, Root, SimpleText, UiText};
+use game_core::level::LevelKind;
 
 use crate::{assets::prelude::*, level::LevelResource, save_file::SaveFile};
 
@@ -11,8 +12,10 @@ pub fn spawn(
 ) {
     let font = fonts.primary();
 
-    let old_record = save_file.get_record(level.kind());
-    let record = if level.is_new_record(&old_record) {
+    let old_record = save_file.get_record(level.kind(), level.mode());
+    let record = if !matches!(level.kind(), LevelKind::Generated(_))
+        && level.is_new_record(&old_record, save_file.get_record_ranking())
+    {
         format!("NEW RECORD:\n{}", level.moves_in_time(' '))
     } else {
         " \n ".to_string()
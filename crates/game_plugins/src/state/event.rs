@@ -26,9 +26,9 @@ impl GameStateTransitionEvent {
         }
     }
 
-    pub fn limit() -> Self {
+    pub fn import() -> Self {
         Self {
-            state: GameState::Limit,
+            state: GameState::Import,
         }
     }
 
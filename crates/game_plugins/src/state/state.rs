@@ -7,8 +7,8 @@ pub enum GameState {
     Title,
     Instructions,
     Editor,
-    Limit,
     Passed,
+    Import,
     Options,
     Selection(SelectionKind),
     Level,
@@ -28,24 +28,16 @@ impl GameState {
 pub enum SelectionKind {
     Stock,
     Custom,
+    /// The levels of one of the packs in the packs folder
+    Pack,
 }
 
 impl SelectionKind {
-    pub fn is_stock(self) -> bool {
-        matches!(self, Self::Stock)
-    }
-
     pub fn to_str(self) -> &'static str {
         match self {
             Self::Stock => "Stock",
             Self::Custom => "Custom",
-        }
-    }
-
-    pub fn toggle(self) -> Self {
-        match self {
-            Self::Stock => Self::Custom,
-            Self::Custom => Self::Stock,
+            Self::Pack => "Pack",
         }
     }
 }
@@ -41,3 +41,30 @@ pub const TRANSPARENT: Color = Color::Srgba(Srgba {
     blue: 0.0,
     alpha: 0.0,
 });
+
+/// Tint of the boxes and zones of each color, the first one leaves the sprites as they are
+pub const TINTS: [Color; 4] = [
+    LIGHT,
+    Color::Srgba(Srgba {
+        red: 255.0 / u8::MAX as f32,
+        green: 120.0 / u8::MAX as f32,
+        blue: 120.0 / u8::MAX as f32,
+        alpha: 1.0,
+    }),
+    Color::Srgba(Srgba {
+        red: 130.0 / u8::MAX as f32,
+        green: 170.0 / u8::MAX as f32,
+        blue: 255.0 / u8::MAX as f32,
+        alpha: 1.0,
+    }),
+    Color::Srgba(Srgba {
+        red: 230.0 / u8::MAX as f32,
+        green: 130.0 / u8::MAX as f32,
+        blue: 255.0 / u8::MAX as f32,
+        alpha: 1.0,
+    }),
+];
+
+pub fn tint(color: u8) -> Color {
+    TINTS.get(color as usize).copied().unwrap_or(LIGHT)
+}